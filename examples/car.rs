@@ -1,26 +1,35 @@
 use ruterm::{
+        buffer::Buffer,
         cursor,
         error::Result,
+        event::{
+                self,
+                Event,
+                KeyCode,
+                KeyEvent,
+        },
         in_raw,
+        panic,
         render::{
                 render,
-                render_with_output,
                 END,
         },
+        signal::Signals,
         size,
+        terminal::Terminal,
         tio,
         view::{
-                color::fore,
-                RESET,
+                Color,
+                Style,
         },
 };
 use std::{
-        io::{
-                self,
-                Stdout,
-        },
+        io,
         thread::sleep,
-        time::Duration,
+        time::{
+                Duration,
+                Instant,
+        },
 };
 
 const CAR_SPEED: u16 = 1;
@@ -28,7 +37,7 @@ const FPS: u64 = 60;
 
 struct State
 {
-        key: Option<u8>,
+        key: Option<KeyCode>,
         width: u16,
         height: u16,
         car_x: u16,
@@ -50,59 +59,51 @@ impl State
         }
 }
 
-#[rustfmt::skip]
-fn draw(car_x: u16, car_y: u16, output: &mut Stdout) -> Result<()>
+fn draw(frame: &mut Buffer, car_x: u16, car_y: u16)
 {
-        cursor::set(car_x, car_y)?;
-        render_with_output(
-                output,
-                vec![
-                        fore::YELLOW, "o", fore::GREEN, "==", fore::YELLOW, "o",
-                        RESET,
-                        END,
-                        fore::GREEN, "|  |",
-                        END,
-                        "|XX|",
-                        RESET,
-                        END,
-                        fore::RED, "*", fore::GREEN, "==", fore::RED, "*", RESET,
-                        END,
-                ],
-        )?;
-        tio::flush_with_output(output)?;
-        Ok(())
+        frame.write(car_x, car_y, "o", Style::new().fg(Color::Yellow));
+        frame.write(car_x + 1, car_y, "==", Style::new().fg(Color::Green));
+        frame.write(car_x + 3, car_y, "o", Style::new().fg(Color::Yellow));
+        frame.write(car_x, car_y + 1, "|  |", Style::new().fg(Color::Green));
+        frame.write(car_x, car_y + 2, "|XX|", Style::new().fg(Color::Green));
+        frame.write(car_x, car_y + 3, "*", Style::new().fg(Color::Red));
+        frame.write(car_x + 1, car_y + 3, "==", Style::new().fg(Color::Green));
+        frame.write(car_x + 3, car_y + 3, "*", Style::new().fg(Color::Red));
 }
 
 fn update(state: &mut State)
 {
         match state.key {
-                Some(b'h') => {
-                        if (state.car_x as i16 - CAR_SPEED as i16) > 0 {
-                                state.car_x -= CAR_SPEED
-                        }
+                Some(KeyCode::Char('h') | KeyCode::Left)
+                        if (state.car_x as i16 - CAR_SPEED as i16) > 0 =>
+                {
+                        state.car_x -= CAR_SPEED
                 }
-                Some(b'j') => {
-                        if (state.car_y + CAR_SPEED) < state.height as u16 {
-                                state.car_y += CAR_SPEED
-                        }
+                Some(KeyCode::Char('j') | KeyCode::Down)
+                        if (state.car_y + CAR_SPEED) < state.height =>
+                {
+                        state.car_y += CAR_SPEED
                 }
-                Some(b'k') => {
-                        if (state.car_y as i16 - CAR_SPEED as i16) > 0 {
-                                state.car_y -= CAR_SPEED
-                        }
+                Some(KeyCode::Char('k') | KeyCode::Up)
+                        if (state.car_y as i16 - CAR_SPEED as i16) > 0 =>
+                {
+                        state.car_y -= CAR_SPEED
                 }
-                Some(b'l') => {
-                        if (state.car_x - CAR_SPEED) < state.width as u16 {
-                                state.car_x += CAR_SPEED
-                        }
+                Some(KeyCode::Char('l') | KeyCode::Right)
+                        if (state.car_x - CAR_SPEED) < state.width =>
+                {
+                        state.car_x += CAR_SPEED
                 }
                 _ => {}
         }
 }
 
-fn delay(delay: u64)
+fn key(event: Option<Event>) -> Option<KeyCode>
 {
-        sleep(Duration::from_millis(delay));
+        match event {
+                Some(Event::Key(KeyEvent { code, .. })) => Some(code),
+                _ => None,
+        }
 }
 
 enum Mode
@@ -112,7 +113,7 @@ enum Mode
 }
 
 #[rustfmt::skip]
-fn start(width: u16, height: u16) -> Result<Mode>
+fn start(signals: &Signals, width: u16, height: u16) -> Result<Mode>
 {
         cursor::set(width / 2 - 11, height / 2)?;
         render(vec![
@@ -127,28 +128,39 @@ fn start(width: u16, height: u16) -> Result<Mode>
         ])?;
         tio::flush()?;
         loop {
-                match tio::read() {
-                        Some(b'q') => return Ok(Mode::Exit),
-                        Some(b'p') => return Ok(Mode::Play),
+                // reads stdin without buffering, so no key is left unseen by the game loop
+                match key(event::wait(signals, Duration::from_secs(1))?) {
+                        Some(KeyCode::Char('q') | KeyCode::Esc) => return Ok(Mode::Exit),
+                        Some(KeyCode::Char('p') | KeyCode::Enter) => return Ok(Mode::Play),
                         _ => {}
                 }
         }
 }
 
-fn game(state: &mut State) -> Result<()>
+fn game(signals: &Signals, state: &mut State) -> Result<()>
 {
-        let mut stdout = io::stdout();
-        let mut stdin = io::stdin();
+        let mut terminal = Terminal::with_output(io::stdout(), state.width, state.height);
+        let frame = Duration::from_millis(1000 / FPS);
         loop {
-                cursor::start()?;
-                draw(state.car_x, state.car_y, &mut stdout)?;
-                state.key = tio::read_with_input(&mut stdin);
-                if state.key == Some(b'q') {
+                let deadline = Instant::now() + frame;
+                terminal.draw(|frame| draw(frame, state.car_x, state.car_y))?;
+                state.key = None;
+                // waits for input until the next frame
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                match event::wait(signals, timeout)? {
+                        // redraw may also follow resizing while stopped
+                        Some(Event::Resize(width, height) | Event::Redraw(width, height)) => {
+                                (state.width, state.height) = (width, height);
+                                terminal.resize(width, height);
+                        }
+                        event => state.key = key(event),
+                }
+                if let Some(KeyCode::Char('q') | KeyCode::Esc) = state.key {
                         cursor::set(0, state.height)?;
                         break;
                 }
                 update(state);
-                delay(1000 / FPS);
+                sleep(deadline.saturating_duration_since(Instant::now()));
         }
         Ok(())
 }
@@ -156,13 +168,15 @@ fn game(state: &mut State) -> Result<()>
 fn main() -> Result<()>
 {
         let mut state = State::new()?;
+        let signals = Signals::new()?;
+        panic::install_hook()?;
 
         in_raw!({
                 cursor::hide()?;
                 cursor::start()?;
 
-                match start(state.width, state.height)? {
-                        Mode::Play => game(&mut state)?,
+                match start(&signals, state.width, state.height)? {
+                        Mode::Play => game(&signals, &mut state)?,
                         Mode::Exit => {}
                 }
 
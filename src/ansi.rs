@@ -0,0 +1,394 @@
+/// Piece of text recognized by [`Parser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action<'a>
+{
+        /// Visible character.
+        Print(char),
+        /// Control character, e.g. `\n` or `\r`.
+        Execute(char),
+        /// Control sequence, e.g. `ESC [ ? 25 h`.
+        Csi
+        {
+                /// Private marker (`<`, `=`, `>` or `?`) placed before parameters.
+                private: Option<char>,
+                /// Parameters separated by `;` or `:`. Omitted parameters are 0.
+                parameters: &'a [u16],
+                intermediates: &'a str,
+                /// Final character.
+                end: char,
+        },
+        /// Escape sequence, e.g. `ESC 7`.
+        Esc
+        {
+                intermediates: &'a str,
+                /// Final character.
+                end: char,
+        },
+        /// Operating system command, e.g. `ESC ] 0 ; title BEL`. Contains text between the
+        /// introducer and the terminator.
+        Osc(&'a str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State
+{
+        Ground,
+        Escape,
+        EscapeIntermediate,
+        Csi,
+        CsiIgnore,
+        Osc,
+        /// Device control, privacy message, application command and start of string. Ignored
+        /// until string terminator.
+        String,
+        /// `ESC` inside of a string, possibly starting its terminator.
+        StringEscape,
+        /// Single shift (SS2 or SS3). Next character is invisible.
+        SingleShift,
+}
+
+const ESCAPE: char = '\x1b';
+const BELL: char = '\x07';
+const CANCEL: char = '\x18';
+const SUBSTITUTE: char = '\x1a';
+
+/// ANSI escape sequence parser.
+///
+/// State machine recognizing CSI, OSC, DCS, SOS/PM/APC and SS2/SS3 sequences of ECMA-48, both in
+/// 7-bit (`ESC [`) and 8-bit (`\u{9b}`) forms. Input can be split at any point.
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::ansi::{
+///         Action,
+///         Parser,
+/// };
+///
+/// let mut parser = Parser::new();
+/// let mut visible = String::new();
+/// for c in "\x1b[1;31mred\x1b[m".chars() {
+///         if let Some(Action::Print(c)) = parser.advance(c) {
+///                 visible.push(c);
+///         }
+/// }
+/// assert_eq!("red", visible);
+/// ```
+///
+/// # References
+///
+/// - [ECMA-48](https://ecma-international.org/publications-and-standards/standards/ecma-48/)
+/// - [DEC ANSI parser](https://vt100.net/emu/dec_ansi_parser)
+#[derive(Debug, Clone)]
+pub struct Parser
+{
+        state: State,
+        /// State to return to after string terminator.
+        string: State,
+        private: Option<char>,
+        parameters: Vec<u16>,
+        parameter: Option<u16>,
+        intermediates: String,
+        text: String,
+}
+
+impl Default for Parser
+{
+        fn default() -> Self
+        {
+                Self::new()
+        }
+}
+
+impl Parser
+{
+        pub fn new() -> Self
+        {
+                Self {
+                        state: State::Ground,
+                        string: State::Ground,
+                        private: None,
+                        parameters: Vec::new(),
+                        parameter: None,
+                        intermediates: String::new(),
+                        text: String::new(),
+                }
+        }
+
+        fn enter(&mut self, state: State)
+        {
+                self.state = state;
+                self.private = None;
+                self.parameters.clear();
+                self.parameter = None;
+                self.intermediates.clear();
+                self.text.clear();
+        }
+
+        fn enter_string(&mut self, kind: State)
+        {
+                self.enter(State::String);
+                self.string = kind;
+        }
+
+        /// Feeds one character. Returns recognized action, if any.
+        pub fn advance(&mut self, c: char) -> Option<Action<'_>>
+        {
+                match self.state {
+                        State::String | State::StringEscape => return self.string(c),
+                        State::Osc => return self.osc(c),
+                        _ => {}
+                }
+                // characters which have the same meaning in any other state
+                match c {
+                        ESCAPE => self.enter(State::Escape),
+                        CANCEL | SUBSTITUTE | '\u{9c}' => self.enter(State::Ground),
+                        '\u{9b}' => self.enter(State::Csi),
+                        '\u{9d}' => self.enter(State::Osc),
+                        '\u{90}' | '\u{98}' | '\u{9e}' | '\u{9f}' => {
+                                self.enter_string(State::Ground)
+                        }
+                        '\u{8e}' | '\u{8f}' => self.enter(State::SingleShift),
+                        '\u{80}'..='\u{9f}' => return Some(Action::Execute(c)),
+                        _ => {
+                                return match self.state {
+                                        State::Ground => match c {
+                                                '\0'..='\x1f' | '\x7f' => Some(Action::Execute(c)),
+                                                _ => Some(Action::Print(c)),
+                                        },
+                                        State::Escape | State::EscapeIntermediate => self.escape(c),
+                                        State::Csi | State::CsiIgnore => self.csi(c),
+                                        _ => {
+                                                self.state = State::Ground;
+                                                None
+                                        }
+                                };
+                        }
+                }
+                None
+        }
+
+        fn escape(&mut self, c: char) -> Option<Action<'_>>
+        {
+                match c {
+                        '\0'..='\x1f' => Some(Action::Execute(c)),
+                        '\x20'..='\x2f' => {
+                                self.intermediates.push(c);
+                                self.state = State::EscapeIntermediate;
+                                None
+                        }
+                        '[' if self.state == State::Escape => {
+                                self.enter(State::Csi);
+                                None
+                        }
+                        ']' if self.state == State::Escape => {
+                                self.enter(State::Osc);
+                                None
+                        }
+                        'P' | 'X' | '^' | '_' if self.state == State::Escape => {
+                                self.enter_string(State::Ground);
+                                None
+                        }
+                        'N' | 'O' if self.state == State::Escape => {
+                                self.enter(State::SingleShift);
+                                None
+                        }
+                        '\x30'..='\x7e' => {
+                                self.state = State::Ground;
+                                Some(Action::Esc {
+                                        intermediates: &self.intermediates,
+                                        end: c,
+                                })
+                        }
+                        '\x7f' => None,
+                        // not a sequence, so the character is printed
+                        _ => {
+                                self.state = State::Ground;
+                                Some(Action::Print(c))
+                        }
+                }
+        }
+
+        fn csi(&mut self, c: char) -> Option<Action<'_>>
+        {
+                match c {
+                        '\0'..='\x1f' => return Some(Action::Execute(c)),
+                        '0'..='9' if self.intermediates.is_empty() => {
+                                let digit = c as u16 - '0' as u16;
+                                let parameter = self.parameter.unwrap_or(0);
+                                self.parameter =
+                                        Some(parameter.saturating_mul(10).saturating_add(digit));
+                        }
+                        ';' | ':' if self.intermediates.is_empty() => {
+                                self.parameters.push(self.parameter.take().unwrap_or(0));
+                        }
+                        '<'..='?' => {
+                                let first = self.parameters.is_empty() && self.parameter.is_none();
+                                if first && self.private.is_none() {
+                                        self.private = Some(c);
+                                }
+                                else {
+                                        self.state = State::CsiIgnore;
+                                }
+                        }
+                        '\x20'..='\x2f' => self.intermediates.push(c),
+                        '\x40'..='\x7e' => {
+                                let ignore = self.state == State::CsiIgnore;
+                                self.state = State::Ground;
+                                if ignore {
+                                        return None;
+                                }
+                                if let Some(parameter) = self.parameter.take() {
+                                        self.parameters.push(parameter);
+                                }
+                                else if !self.parameters.is_empty() {
+                                        // trailing separator
+                                        self.parameters.push(0);
+                                }
+                                return Some(Action::Csi {
+                                        private: self.private,
+                                        parameters: &self.parameters,
+                                        intermediates: &self.intermediates,
+                                        end: c,
+                                });
+                        }
+                        _ => self.state = State::CsiIgnore,
+                }
+                None
+        }
+
+        fn osc(&mut self, c: char) -> Option<Action<'_>>
+        {
+                match c {
+                        BELL | '\u{9c}' => {
+                                self.state = State::Ground;
+                                Some(Action::Osc(&self.text))
+                        }
+                        ESCAPE => {
+                                self.string = State::Osc;
+                                self.state = State::StringEscape;
+                                None
+                        }
+                        CANCEL | SUBSTITUTE => {
+                                self.enter(State::Ground);
+                                None
+                        }
+                        _ => {
+                                self.text.push(c);
+                                None
+                        }
+                }
+        }
+
+        fn string(&mut self, c: char) -> Option<Action<'_>>
+        {
+                let escape = self.state == State::StringEscape;
+                match c {
+                        '\\' if escape => {
+                                self.state = State::Ground;
+                                if self.string == State::Osc {
+                                        return Some(Action::Osc(&self.text));
+                                }
+                                None
+                        }
+                        _ if escape => {
+                                // not a terminator, start of a new sequence
+                                self.enter(State::Escape);
+                                self.escape(c)
+                        }
+                        ESCAPE => {
+                                self.state = State::StringEscape;
+                                None
+                        }
+                        '\u{9c}' | CANCEL | SUBSTITUTE => {
+                                self.enter(State::Ground);
+                                None
+                        }
+                        _ => None,
+                }
+        }
+}
+
+/// Removes escape sequences from `text`, keeping visible and control characters.
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::ansi::strip_ansi;
+///
+/// let link = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+/// assert_eq!("link", strip_ansi(link));
+/// ```
+pub fn strip_ansi(text: &str) -> String
+{
+        let mut parser = Parser::new();
+        let mut stripped = String::with_capacity(text.len());
+        for c in text.chars() {
+                if let Some(Action::Print(c) | Action::Execute(c)) = parser.advance(c) {
+                        stripped.push(c);
+                }
+        }
+        stripped
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        fn strip_ansi_()
+        {
+                assert_eq!(
+                        "title:Kept\n",
+                        strip_ansi(concat!(
+                                "\x1b]0;window title\x07",
+                                "title:",
+                                "\x1b[2K",
+                                "\x1b[10G",
+                                "\x1b[38;2;255;0;0m",
+                                "Kept",
+                                "\x1bP1$r\x1b\\",
+                                "\x1bOP",
+                                "\x1b7",
+                                "\n"
+                        ))
+                );
+                assert_eq!("ébc", strip_ansi("\x1bébc"));
+        }
+
+        #[test]
+        fn csi_()
+        {
+                let mut parser = Parser::new();
+                let mut actions = Vec::new();
+                for c in "\x1b[?1049h\x1b[;5H".chars() {
+                        if let Some(action) = parser.advance(c) {
+                                actions.push(format!("{action:?}"));
+                        }
+                }
+                assert_eq!(
+                        vec![
+                                format!(
+                                        "{:?}",
+                                        Action::Csi {
+                                                private: Some('?'),
+                                                parameters: &[1049],
+                                                intermediates: "",
+                                                end: 'h'
+                                        }
+                                ),
+                                format!(
+                                        "{:?}",
+                                        Action::Csi {
+                                                private: None,
+                                                parameters: &[0, 5],
+                                                intermediates: "",
+                                                end: 'H'
+                                        }
+                                ),
+                        ],
+                        actions
+                );
+        }
+}
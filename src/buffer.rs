@@ -0,0 +1,178 @@
+use crate::view::Style;
+use unicode_width::UnicodeWidthChar;
+
+/// Single character on the screen with its style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell
+{
+        pub symbol: char,
+        pub style: Style,
+}
+
+impl Cell
+{
+        /// Symbol of the cell covered by the right half of a wide character.
+        pub const CONTINUATION: char = '\0';
+
+        pub fn new(symbol: char, style: Style) -> Self
+        {
+                Self { symbol, style }
+        }
+
+        /// Cell covered by the right half of a wide character. It's not drawn.
+        pub fn continuation(style: Style) -> Self
+        {
+                Self::new(Self::CONTINUATION, style)
+        }
+
+        pub fn is_continuation(&self) -> bool
+        {
+                self.symbol == Self::CONTINUATION
+        }
+
+        /// Number of columns taken by the symbol: 2 for wide characters (e.g. CJK and emoji), 0
+        /// for continuation cells and 1 otherwise.
+        pub fn width(&self) -> u16
+        {
+                match self.symbol.width() {
+                        _ if self.is_continuation() => 0,
+                        Some(2) => 2,
+                        _ => 1,
+                }
+        }
+}
+
+impl Default for Cell
+{
+        fn default() -> Self
+        {
+                Self::new(' ', Style::new())
+        }
+}
+
+/// Grid of cells. Drawn by [`crate::terminal::Terminal`].
+///
+/// Coordinates start from 0 in the top left corner.
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::{
+///         buffer::Buffer,
+///         view::{
+///                 Color,
+///                 Style,
+///         },
+/// };
+///
+/// let mut buffer = Buffer::new(10, 2);
+/// buffer.write(0, 0, "Hello", Style::new().fg(Color::Green));
+/// assert_eq!('H', buffer.get(0, 0).unwrap().symbol);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer
+{
+        width: u16,
+        height: u16,
+        cells: Vec<Cell>,
+}
+
+impl Buffer
+{
+        pub fn new(width: u16, height: u16) -> Self
+        {
+                Self {
+                        width,
+                        height,
+                        cells: vec![Cell::default(); width as usize * height as usize],
+                }
+        }
+
+        pub fn width(&self) -> u16
+        {
+                self.width
+        }
+
+        pub fn height(&self) -> u16
+        {
+                self.height
+        }
+
+        fn index(&self, x: u16, y: u16) -> Option<usize>
+        {
+                (x < self.width && y < self.height)
+                        .then_some(y as usize * self.width as usize + x as usize)
+        }
+
+        pub fn get(&self, x: u16, y: u16) -> Option<&Cell>
+        {
+                self.index(x, y).map(|i| &self.cells[i])
+        }
+
+        pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell>
+        {
+                self.index(x, y).map(|i| &mut self.cells[i])
+        }
+
+        /// Sets cell at given position. Cells outside of the buffer are ignored.
+        ///
+        /// Overwriting one half of a wide character blanks the other half.
+        pub fn set(&mut self, x: u16, y: u16, cell: Cell)
+        {
+                let index = match self.index(x, y) {
+                        Some(index) => index,
+                        None => return,
+                };
+                let previous = self.cells[index];
+                // continuation is set right after its wide character
+                if previous.is_continuation() && !cell.is_continuation() && x > 0 {
+                        self.blank(index - 1, |cell| cell.width() == 2);
+                }
+                if previous.width() == 2 && x + 1 < self.width {
+                        self.blank(index + 1, Cell::is_continuation);
+                }
+                self.cells[index] = cell;
+        }
+
+        fn blank(&mut self, index: usize, condition: fn(&Cell) -> bool)
+        {
+                let cell = &mut self.cells[index];
+                if condition(cell) {
+                        *cell = Cell::new(' ', cell.style);
+                }
+        }
+
+        /// Writes `text` starting at given position. Text outside of the buffer is clipped.
+        ///
+        /// Wide characters take two cells, the second one is a continuation cell (see
+        /// [`Cell::continuation()`]). Zero-width characters, e.g. combining marks, are skipped.
+        pub fn write(&mut self, x: u16, y: u16, text: &str, style: Style)
+        {
+                let mut x = x;
+                for symbol in text.chars() {
+                        let width = match symbol.width() {
+                                Some(0) | None => continue,
+                                Some(width) => width as u16,
+                        };
+                        if x >= self.width {
+                                break;
+                        }
+                        if width == 2 && x + 1 == self.width {
+                                // only half of the character fits
+                                self.set(x, y, Cell::new(' ', style));
+                                break;
+                        }
+                        self.set(x, y, Cell::new(symbol, style));
+                        if width == 2 {
+                                self.set(x + 1, y, Cell::continuation(style));
+                        }
+                        x += width;
+                }
+        }
+
+        /// Resets all cells to blank.
+        pub fn clear(&mut self)
+        {
+                self.cells.fill(Cell::default());
+        }
+}
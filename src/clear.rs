@@ -0,0 +1,116 @@
+use crate::{
+        error::Result,
+        tio::write_with_output,
+};
+use std::io::{
+        self,
+        Write,
+};
+
+/// Clears the whole screen. Writes to `output`. Same as [`all()`].
+pub fn all_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[2J")
+}
+
+/// Clears the whole screen without moving the cursor. Writes to stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::clear;
+///
+/// clear::all().unwrap();
+/// ```
+pub fn all() -> Result<usize>
+{
+        all_with_output(&mut io::stdout())
+}
+
+/// Clears from the cursor to the end of the screen. Writes to `output`. Same as [`below()`].
+pub fn below_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[J")
+}
+
+/// Clears from the cursor to the end of the screen. Writes to stdout.
+pub fn below() -> Result<usize>
+{
+        below_with_output(&mut io::stdout())
+}
+
+/// Clears from the beginning of the screen to the cursor. Writes to `output`. Same as [`above()`].
+pub fn above_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[1J")
+}
+
+/// Clears from the beginning of the screen to the cursor. Writes to stdout.
+pub fn above() -> Result<usize>
+{
+        above_with_output(&mut io::stdout())
+}
+
+/// Clears scrollback buffer. Writes to `output`. Same as [`scrollback()`].
+pub fn scrollback_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[3J")
+}
+
+/// Clears scrollback buffer, leaving the screen as is. Writes to stdout.
+pub fn scrollback() -> Result<usize>
+{
+        scrollback_with_output(&mut io::stdout())
+}
+
+/// Clears the cursor line. Writes to `output`. Same as [`line()`].
+pub fn line_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[2K")
+}
+
+/// Clears the cursor line without moving the cursor. Writes to stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         clear,
+///         cursor,
+///         tio::write,
+/// };
+///
+/// cursor::set(1, 1).unwrap();
+/// clear::line().unwrap();
+/// write("Updated status").unwrap();
+/// ```
+pub fn line() -> Result<usize>
+{
+        line_with_output(&mut io::stdout())
+}
+
+/// Clears from the cursor to the end of the line. Writes to `output`. Same as
+/// [`to_end_of_line()`].
+pub fn to_end_of_line_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[K")
+}
+
+/// Clears from the cursor to the end of the line. Writes to stdout.
+pub fn to_end_of_line() -> Result<usize>
+{
+        to_end_of_line_with_output(&mut io::stdout())
+}
+
+/// Clears from the beginning of the line to the cursor. Writes to `output`. Same as
+/// [`to_start_of_line()`].
+pub fn to_start_of_line_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[1K")
+}
+
+/// Clears from the beginning of the line to the cursor. Writes to stdout.
+pub fn to_start_of_line() -> Result<usize>
+{
+        to_start_of_line_with_output(&mut io::stdout())
+}
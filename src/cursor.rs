@@ -1,6 +1,13 @@
 use crate::{
-        error::Result,
-        tio::write_with_output,
+        error::{
+                Error,
+                Result,
+        },
+        tio::{
+                flush_with_output,
+                read_reply_with_input,
+                write_with_output,
+        },
 };
 use std::fmt::{
         Display,
@@ -10,6 +17,18 @@ use std::io::{
         self,
         Write,
 };
+use std::os::fd::AsRawFd;
+use std::sync::atomic::{
+        AtomicBool,
+        Ordering,
+};
+use std::time::Duration;
+
+/// Whether the cursor was hidden last. Restored by [`crate::screen::AlternateScreen::suspend()`].
+pub(crate) static HIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// How long [`position()`] waits for the terminal to reply.
+pub const POSITION_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Sets cursor position. Writes to `output`. Same as [`set()`].
 pub fn set_with_output(output: &mut dyn Write, x: u16, y: u16) -> Result<usize>
@@ -93,7 +112,7 @@ pub fn move_(direction: Direction, distance: u16) -> Result<usize>
 /// Moves cursor on the start position and clears the screen. Writes to `output`. Same as [`start()`].
 pub fn start_with_output(output: &mut dyn Write) -> Result<usize>
 {
-        write_with_output(output, "\x1b[2J")
+        write_with_output(output, "\x1b[H\x1b[2J")
 }
 
 /// Moves cursor on the start position and clears the screen. Writes to stdout.
@@ -105,6 +124,7 @@ pub fn start() -> Result<usize>
 /// Makes cursor invisible. Writes to `output`. Same as [`hide()`].
 pub fn hide_with_output(output: &mut dyn Write) -> Result<usize>
 {
+        HIDDEN.store(true, Ordering::Relaxed);
         write_with_output(output, "\x1b[?25l")
 }
 
@@ -117,6 +137,7 @@ pub fn hide() -> Result<usize>
 /// Makes cursor visible. Writes to `output`. Same as [`show()`].
 pub fn show_with_output(output: &mut dyn Write) -> Result<usize>
 {
+        HIDDEN.store(false, Ordering::Relaxed);
         write_with_output(output, "\x1b[?25h")
 }
 
@@ -125,3 +146,220 @@ pub fn show() -> Result<usize>
 {
         show_with_output(&mut io::stdout())
 }
+
+/// Saves cursor position and text attributes. Writes to `output`. Same as [`save()`].
+pub fn save_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b7")
+}
+
+/// Saves cursor position and text attributes (DECSC). Writes to stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         cursor,
+///         tio::write,
+/// };
+///
+/// cursor::save().unwrap();
+/// cursor::set(1, 1).unwrap();
+/// write("Status").unwrap();
+/// cursor::restore().unwrap(); // back to the saved position
+/// ```
+pub fn save() -> Result<usize>
+{
+        save_with_output(&mut io::stdout())
+}
+
+/// Restores cursor saved by [`save()`]. Writes to `output`. Same as [`restore()`].
+pub fn restore_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b8")
+}
+
+/// Restores cursor saved by [`save()`] (DECRC). Writes to stdout.
+pub fn restore() -> Result<usize>
+{
+        restore_with_output(&mut io::stdout())
+}
+
+/// Saves cursor position. Writes to `output`. Same as [`save_position()`].
+pub fn save_position_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[s")
+}
+
+/// Saves cursor position only (SCOSC). Writes to stdout.
+///
+/// Same as [`save()`], but leaves text attributes alone. Some terminals don't support it.
+pub fn save_position() -> Result<usize>
+{
+        save_position_with_output(&mut io::stdout())
+}
+
+/// Restores cursor position saved by [`save_position()`]. Writes to `output`. Same as
+/// [`restore_position()`].
+pub fn restore_position_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[u")
+}
+
+/// Restores cursor position saved by [`save_position()`] (SCORC). Writes to stdout.
+pub fn restore_position() -> Result<usize>
+{
+        restore_position_with_output(&mut io::stdout())
+}
+
+/// Cursor shape. Used in [`set_shape()`].
+pub enum Shape
+{
+        /// Shape configured by the user.
+        Default,
+        BlinkingBlock,
+        SteadyBlock,
+        BlinkingUnderline,
+        SteadyUnderline,
+        BlinkingBar,
+        SteadyBar,
+}
+
+impl Display for Shape
+{
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+        {
+                write!(
+                        f,
+                        "{}",
+                        match self {
+                                Self::Default => 0,
+                                Self::BlinkingBlock => 1,
+                                Self::SteadyBlock => 2,
+                                Self::BlinkingUnderline => 3,
+                                Self::SteadyUnderline => 4,
+                                Self::BlinkingBar => 5,
+                                Self::SteadyBar => 6,
+                        }
+                )
+        }
+}
+
+/// Sets cursor shape. Writes to `output`. Same as [`set_shape()`].
+pub fn set_shape_with_output(output: &mut dyn Write, shape: Shape) -> Result<usize>
+{
+        write_with_output(output, format!("\x1b[{} q", shape))
+}
+
+/// Sets cursor shape (DECSCUSR). Writes to stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::cursor::{
+///         self,
+///         Shape,
+/// };
+///
+/// cursor::set_shape(Shape::SteadyBar).unwrap();
+/// // ...
+/// cursor::set_shape(Shape::Default).unwrap();
+/// ```
+pub fn set_shape(shape: Shape) -> Result<usize>
+{
+        set_shape_with_output(&mut io::stdout(), shape)
+}
+
+/// Sets cursor color. Writes to `output`. Same as [`set_color()`].
+pub fn set_color_with_output(output: &mut dyn Write, r: u8, g: u8, b: u8) -> Result<usize>
+{
+        write_with_output(output, format!("\x1b]12;#{:02x}{:02x}{:02x}\x07", r, g, b))
+}
+
+/// Sets cursor color (OSC 12). Writes to stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::cursor;
+///
+/// cursor::set_color(255, 128, 0).unwrap();
+/// // ...
+/// cursor::reset_color().unwrap();
+/// ```
+pub fn set_color(r: u8, g: u8, b: u8) -> Result<usize>
+{
+        set_color_with_output(&mut io::stdout(), r, g, b)
+}
+
+/// Restores default cursor color. Writes to `output`. Same as [`reset_color()`].
+pub fn reset_color_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b]112\x07")
+}
+
+/// Restores default cursor color (OSC 112). Writes to stdout.
+pub fn reset_color() -> Result<usize>
+{
+        reset_color_with_output(&mut io::stdout())
+}
+
+/// Parses `ESC [ row ; column R` into (x, y).
+fn parse_position(reply: &[u8]) -> Option<(u16, u16)>
+{
+        let reply = std::str::from_utf8(reply).ok()?;
+        let (row, column) = reply
+                .strip_prefix("\x1b[")?
+                .strip_suffix('R')?
+                .split_once(';')?;
+        Some((column.parse().ok()?, row.parse().ok()?))
+}
+
+/// Gets cursor position. Writes query to `output` and reads reply from `input`. Same as
+/// [`position()`].
+pub fn position_with_io(output: &mut dyn Write, input: &impl AsRawFd) -> Result<(u16, u16)>
+{
+        write_with_output(output, "\x1b[6n")?;
+        flush_with_output(output)?;
+        let reply = read_reply_with_input(input, b'R', POSITION_TIMEOUT)?
+                .ok_or(Error::Timeout("cursor position query"))?;
+        parse_position(&reply).ok_or(Error::Parse("cursor position report"))
+}
+
+/// Gets cursor position. Writes to stdout and reads from stdin.
+///
+/// Returns (x, y), same as in [`set()`]. Should be used in raw mode, otherwise the reply is not
+/// available until Enter is pressed. Fails if the terminal doesn't reply in [`POSITION_TIMEOUT`].
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::cursor;
+///
+/// let (x, y) = cursor::position().unwrap();
+/// ```
+///
+/// # Note
+///
+/// Input which arrives before the reply is discarded.
+pub fn position() -> Result<(u16, u16)>
+{
+        position_with_io(&mut io::stdout(), &io::stdin())
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+        use std::os::unix::net::UnixStream;
+
+        #[test]
+        fn position_()
+        {
+                let (mut terminal, input) = UnixStream::pair().unwrap();
+                terminal.write_all(b"typed\x1b[12;40R").unwrap();
+                let mut output = Vec::new();
+                assert_eq!((40, 12), position_with_io(&mut output, &input).unwrap());
+                assert_eq!(b"\x1b[6n".to_vec(), output);
+        }
+}
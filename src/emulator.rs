@@ -0,0 +1,594 @@
+use crate::{
+        ansi::{
+                Action,
+                Parser,
+        },
+        buffer::{
+                Buffer,
+                Cell,
+        },
+        view::{
+                Color,
+                Modifier,
+                Style,
+        },
+};
+use std::io::{
+        self,
+        Write,
+};
+use unicode_width::UnicodeWidthChar;
+
+const TAB: u16 = 8;
+
+/// Cursor position and style saved by `ESC 7`.
+#[derive(Debug, Clone, Copy)]
+struct Saved
+{
+        x: u16,
+        y: u16,
+        style: Style,
+}
+
+/// Headless terminal emulator.
+///
+/// Interprets text and escape sequences written to it (cursor movement, SGR, erase, scroll and
+/// alternate screen) into a grid of cells, so tests can check what the user would see.
+///
+/// Coordinates start from 0 in the top left corner, same as in [`Buffer`].
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::{
+///         cursor,
+///         emulator::Emulator,
+///         tio::write_with_output,
+///         view::{
+///                 color::fore,
+///                 Color,
+///         },
+/// };
+///
+/// let mut emulator = Emulator::new(20, 3);
+/// cursor::set_with_output(&mut emulator, 3, 2).unwrap();
+/// write_with_output(&mut emulator, fore::RED.to_string() + "Hi").unwrap();
+/// assert_eq!("\n  Hi", emulator.text());
+/// assert_eq!(Color::Red, emulator.cell(2, 1).unwrap().style.fg);
+/// ```
+pub struct Emulator
+{
+        parser: Parser,
+        /// Bytes of incomplete UTF-8 character.
+        pending: Vec<u8>,
+        screen: Buffer,
+        /// Main screen, while the alternate one is shown.
+        main: Option<(Buffer, Saved)>,
+        x: u16,
+        y: u16,
+        /// Cursor is past the last column, next character goes to the next line.
+        wrap: bool,
+        style: Style,
+        saved: Saved,
+        visible: bool,
+        top: u16,
+        bottom: u16,
+}
+
+impl Emulator
+{
+        pub fn new(width: u16, height: u16) -> Self
+        {
+                Self {
+                        parser: Parser::new(),
+                        pending: Vec::new(),
+                        screen: Buffer::new(width, height),
+                        main: None,
+                        x: 0,
+                        y: 0,
+                        wrap: false,
+                        style: Style::new(),
+                        saved: Saved {
+                                x: 0,
+                                y: 0,
+                                style: Style::new(),
+                        },
+                        visible: true,
+                        top: 0,
+                        bottom: height.saturating_sub(1),
+                }
+        }
+
+        /// Visible screen.
+        pub fn buffer(&self) -> &Buffer
+        {
+                &self.screen
+        }
+
+        pub fn cell(&self, x: u16, y: u16) -> Option<&Cell>
+        {
+                self.screen.get(x, y)
+        }
+
+        /// Text of line `y` without trailing spaces.
+        pub fn line(&self, y: u16) -> String
+        {
+                let line: String = (0..self.screen.width())
+                        .filter_map(|x| self.screen.get(x, y))
+                        .filter(|cell| !cell.is_continuation())
+                        .map(|cell| cell.symbol)
+                        .collect();
+                line.trim_end().to_string()
+        }
+
+        /// Text of the screen without trailing spaces and empty lines.
+        pub fn text(&self) -> String
+        {
+                let lines: Vec<String> = (0..self.screen.height()).map(|y| self.line(y)).collect();
+                lines.join("\n").trim_end().to_string()
+        }
+
+        /// Cursor position (x, y).
+        pub fn cursor(&self) -> (u16, u16)
+        {
+                (self.x, self.y)
+        }
+
+        pub fn is_cursor_visible(&self) -> bool
+        {
+                self.visible
+        }
+
+        pub fn is_alternate_screen(&self) -> bool
+        {
+                self.main.is_some()
+        }
+
+        fn width(&self) -> u16
+        {
+                self.screen.width()
+        }
+
+        fn height(&self) -> u16
+        {
+                self.screen.height()
+        }
+
+        fn feed(&mut self, c: char)
+        {
+                // actions borrow the parser, so they are copied out before being applied
+                let action = match self.parser.advance(c) {
+                        None => return,
+                        Some(Action::Print(c)) => Applied::Print(c),
+                        Some(Action::Execute(c)) => Applied::Execute(c),
+                        Some(Action::Csi {
+                                private,
+                                parameters,
+                                intermediates: "",
+                                end,
+                        }) => Applied::Csi(private, parameters.to_vec(), end),
+                        Some(Action::Esc {
+                                intermediates: "",
+                                end,
+                        }) => Applied::Esc(end),
+                        Some(_) => return,
+                };
+                match action {
+                        Applied::Print(c) => self.print(c),
+                        Applied::Execute(c) => self.execute(c),
+                        Applied::Csi(None, parameters, end) => self.csi(&parameters, end),
+                        Applied::Csi(Some('?'), parameters, end) => self.mode(&parameters, end),
+                        Applied::Csi(..) => {}
+                        Applied::Esc(end) => self.escape(end),
+                }
+        }
+
+        fn print(&mut self, c: char)
+        {
+                let width = match c.width() {
+                        // a cell holds one character, so combining marks are dropped
+                        Some(0) => return,
+                        Some(2) => 2,
+                        _ => 1,
+                };
+                // wide character doesn't fit in the last column
+                if self.wrap || self.x as u32 + width > self.width() as u32 {
+                        self.x = 0;
+                        self.line_feed();
+                }
+                self.screen.set(self.x, self.y, Cell::new(c, self.style));
+                if width == 2 {
+                        self.screen
+                                .set(self.x + 1, self.y, Cell::continuation(self.style));
+                }
+                if (self.x as u32 + width) < self.width() as u32 {
+                        self.x += width as u16;
+                }
+                else {
+                        self.x = self.width().saturating_sub(1);
+                        self.wrap = true;
+                }
+        }
+
+        fn execute(&mut self, c: char)
+        {
+                match c {
+                        '\n' | '\x0b' | '\x0c' => self.line_feed(),
+                        '\r' => self.move_to(0, self.y),
+                        '\x08' => self.move_to(self.x.saturating_sub(1), self.y),
+                        '\t' => {
+                                let x = (self.x / TAB + 1).saturating_mul(TAB);
+                                self.move_to(x, self.y)
+                        }
+                        _ => {}
+                }
+        }
+
+        fn move_to(&mut self, x: u16, y: u16)
+        {
+                self.x = x.min(self.width().saturating_sub(1));
+                self.y = y.min(self.height().saturating_sub(1));
+                self.wrap = false;
+        }
+
+        fn line_feed(&mut self)
+        {
+                self.wrap = false;
+                if self.y == self.bottom {
+                        self.scroll_up(1);
+                }
+                else if self.y + 1 < self.height() {
+                        self.y += 1;
+                }
+        }
+
+        fn clear(&mut self, from: (u16, u16), to: (u16, u16))
+        {
+                let width = self.width() as usize;
+                if width == 0 {
+                        return;
+                }
+                let index = |(x, y): (u16, u16)| y as usize * width + x as usize;
+                for i in index(from)..index(to) {
+                        let (x, y) = ((i % width) as u16, (i / width) as u16);
+                        self.screen.set(x, y, Cell::new(' ', self.style));
+                }
+        }
+
+        fn copy_line(&mut self, from: u16, to: u16)
+        {
+                for x in 0..self.width() {
+                        let cell = *self.screen.get(x, from).unwrap();
+                        self.screen.set(x, to, cell);
+                }
+        }
+
+        fn scroll_up(&mut self, lines: u16)
+        {
+                if self.height() == 0 {
+                        return;
+                }
+                // lines which are scrolled out of the region entirely are just cleared
+                let lines = lines.min(self.bottom + 1 - self.top);
+                for y in self.top..self.bottom + 1 - lines {
+                        self.copy_line(y + lines, y);
+                }
+                self.clear((0, self.bottom + 1 - lines), (0, self.bottom + 1));
+        }
+
+        fn scroll_down(&mut self, lines: u16)
+        {
+                if self.height() == 0 {
+                        return;
+                }
+                let lines = lines.min(self.bottom + 1 - self.top);
+                for y in (self.top + lines..=self.bottom).rev() {
+                        self.copy_line(y - lines, y);
+                }
+                self.clear((0, self.top), (0, self.top + lines));
+        }
+
+        fn csi(&mut self, parameters: &[u16], end: char)
+        {
+                let parameter = |i: usize, default: u16| match parameters.get(i) {
+                        Some(0) | None => default,
+                        Some(p) => *p,
+                };
+                let (x, y) = (self.x, self.y);
+                let height = self.height();
+                match end {
+                        'H' | 'f' => self.move_to(parameter(1, 1) - 1, parameter(0, 1) - 1),
+                        'A' => self.move_to(x, y.saturating_sub(parameter(0, 1))),
+                        'B' => self.move_to(x, y.saturating_add(parameter(0, 1))),
+                        'C' => self.move_to(x.saturating_add(parameter(0, 1)), y),
+                        'D' => self.move_to(x.saturating_sub(parameter(0, 1)), y),
+                        'G' => self.move_to(parameter(0, 1) - 1, y),
+                        'd' => self.move_to(x, parameter(0, 1) - 1),
+                        'J' => match parameter(0, 0) {
+                                0 => self.clear((x, y), (0, height)),
+                                1 => self.clear((0, 0), (x + 1, y)),
+                                2 | 3 => self.clear((0, 0), (0, height)),
+                                _ => {}
+                        },
+                        'K' => match parameter(0, 0) {
+                                0 => self.clear((x, y), (0, y + 1)),
+                                1 => self.clear((0, y), (x + 1, y)),
+                                2 => self.clear((0, y), (0, y + 1)),
+                                _ => {}
+                        },
+                        'S' => self.scroll_up(parameter(0, 1)),
+                        'T' => self.scroll_down(parameter(0, 1)),
+                        'r' => {
+                                let top = parameter(0, 1) - 1;
+                                let bottom = parameter(1, height).min(height).saturating_sub(1);
+                                if top < bottom {
+                                        (self.top, self.bottom) = (top, bottom);
+                                }
+                                self.move_to(0, 0);
+                        }
+                        's' => self.escape('7'),
+                        'u' => self.escape('8'),
+                        'm' => self.sgr(parameters),
+                        _ => {}
+                }
+        }
+
+        fn mode(&mut self, parameters: &[u16], end: char)
+        {
+                let enable = match end {
+                        'h' => true,
+                        'l' => false,
+                        _ => return,
+                };
+                for parameter in parameters {
+                        match parameter {
+                                25 => self.visible = enable,
+                                1049 if enable && self.main.is_none() => {
+                                        let blank = Buffer::new(self.width(), self.height());
+                                        let main = std::mem::replace(&mut self.screen, blank);
+                                        let cursor = Saved {
+                                                x: self.x,
+                                                y: self.y,
+                                                style: self.style,
+                                        };
+                                        self.main = Some((main, cursor));
+                                }
+                                1049 if !enable => {
+                                        if let Some((main, cursor)) = self.main.take() {
+                                                self.screen = main;
+                                                self.move_to(cursor.x, cursor.y);
+                                                self.style = cursor.style;
+                                        }
+                                }
+                                _ => {}
+                        }
+                }
+        }
+
+        fn escape(&mut self, end: char)
+        {
+                match end {
+                        '7' => {
+                                self.saved = Saved {
+                                        x: self.x,
+                                        y: self.y,
+                                        style: self.style,
+                                }
+                        }
+                        '8' => {
+                                let saved = self.saved;
+                                self.move_to(saved.x, saved.y);
+                                self.style = saved.style;
+                        }
+                        'c' => *self = Self::new(self.width(), self.height()),
+                        _ => {}
+                }
+        }
+
+        fn sgr(&mut self, parameters: &[u16])
+        {
+                if parameters.is_empty() {
+                        self.style = Style::new();
+                        return;
+                }
+                let mut parameters = parameters.iter().copied();
+                while let Some(parameter) = parameters.next() {
+                        let style = &mut self.style;
+                        match parameter {
+                                0 => *style = Style::new(),
+                                1 => style.modifier |= Modifier::BOLD,
+                                2 => style.modifier |= Modifier::DIM,
+                                3 => style.modifier |= Modifier::ITALIC,
+                                4 => style.modifier |= Modifier::UNDERLINE,
+                                5 => style.modifier |= Modifier::BLINK,
+                                7 => style.modifier |= Modifier::REVERSE,
+                                9 => style.modifier |= Modifier::STRIKE,
+                                22 => style.modifier.remove(Modifier::BOLD | Modifier::DIM),
+                                23 => style.modifier.remove(Modifier::ITALIC),
+                                24 => style.modifier.remove(Modifier::UNDERLINE),
+                                25 => style.modifier.remove(Modifier::BLINK),
+                                27 => style.modifier.remove(Modifier::REVERSE),
+                                29 => style.modifier.remove(Modifier::STRIKE),
+                                30..=37 => style.fg = named(parameter - 30),
+                                38 => style.fg = extended(&mut parameters),
+                                39 => style.fg = Color::Reset,
+                                40..=47 => style.bg = named(parameter - 40),
+                                48 => style.bg = extended(&mut parameters),
+                                49 => style.bg = Color::Reset,
+                                90..=97 => style.fg = named(parameter - 90 + 8),
+                                100..=107 => style.bg = named(parameter - 100 + 8),
+                                _ => {}
+                        }
+                }
+        }
+}
+
+/// Owned copy of [`Action`] applied by [`Emulator`].
+enum Applied
+{
+        Print(char),
+        Execute(char),
+        Csi(Option<char>, Vec<u16>, char),
+        Esc(char),
+}
+
+/// Color of the 16-color palette.
+fn named(index: u16) -> Color
+{
+        [
+                Color::Black,
+                Color::Red,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::White,
+                Color::BrightBlack,
+                Color::BrightRed,
+                Color::BrightGreen,
+                Color::BrightYellow,
+                Color::BrightBlue,
+                Color::BrightMagenta,
+                Color::BrightCyan,
+                Color::BrightWhite,
+        ][index as usize % 16]
+}
+
+/// Parses `5;n` and `2;r;g;b` after 38 and 48.
+fn extended(parameters: &mut impl Iterator<Item = u16>) -> Color
+{
+        let mut next = || parameters.next().unwrap_or(0).min(255) as u8;
+        match next() {
+                5 => Color::Indexed(next()),
+                2 => Color::Rgb(next(), next(), next()),
+                _ => Color::Reset,
+        }
+}
+
+impl Write for Emulator
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                self.pending.extend_from_slice(bytes);
+                let pending = std::mem::take(&mut self.pending);
+                let mut rest = pending.as_slice();
+                loop {
+                        match std::str::from_utf8(rest) {
+                                Ok(text) => {
+                                        text.chars().for_each(|c| self.feed(c));
+                                        break;
+                                }
+                                Err(error) => {
+                                        let (valid, invalid) = rest.split_at(error.valid_up_to());
+                                        if let Ok(valid) = std::str::from_utf8(valid) {
+                                                valid.chars().for_each(|c| self.feed(c));
+                                        }
+                                        match error.error_len() {
+                                                Some(length) => {
+                                                        self.feed(char::REPLACEMENT_CHARACTER);
+                                                        rest = &invalid[length..];
+                                                }
+                                                None => {
+                                                        // incomplete character, wait for the rest
+                                                        self.pending = invalid.to_vec();
+                                                        break;
+                                                }
+                                        }
+                                }
+                        }
+                }
+                Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                Ok(())
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "render")]
+        fn render_()
+        {
+                use crate::{
+                        cursor,
+                        render::{
+                                render_with_output,
+                                END,
+                        },
+                        view::color::fore,
+                };
+
+                let mut emulator = Emulator::new(10, 4);
+                cursor::set_with_output(&mut emulator, 3, 2).unwrap();
+                render_with_output(
+                        &mut emulator,
+                        vec![fore::GREEN, "* *", END, " * ", END, "* *"],
+                )
+                .unwrap();
+                assert_eq!("\n  * *\n   *\n  * *", emulator.text());
+                assert_eq!(Color::Green, emulator.cell(3, 2).unwrap().style.fg);
+                assert_eq!((5, 3), emulator.cursor());
+        }
+
+        #[test]
+        fn alternate_screen_()
+        {
+                let mut emulator = Emulator::new(10, 2);
+                emulator.write_all(b"main\x1b[?1049h\x1b[H\x1b[?25lalt\x1b[2;1H\x1b[1;31mx")
+                        .unwrap();
+                assert!(emulator.is_alternate_screen());
+                assert!(!emulator.is_cursor_visible());
+                assert_eq!("alt\nx", emulator.text());
+                assert!(emulator
+                        .cell(0, 1)
+                        .unwrap()
+                        .style
+                        .modifier
+                        .contains(Modifier::BOLD));
+                emulator.write_all(b"\x1b[?1049l").unwrap();
+                assert_eq!("main", emulator.text());
+        }
+
+        #[test]
+        fn scroll_()
+        {
+                let mut emulator = Emulator::new(4, 3);
+                emulator.write_all("1\r\n2\r\n3\r\n4".as_bytes()).unwrap();
+                assert_eq!("2\n3\n4", emulator.text());
+                emulator.write_all(b"\x1b[2K\x1b[1;1H\x1b[K\x1b[T").unwrap();
+                assert_eq!("\n\n3", emulator.text());
+        }
+
+        #[test]
+        fn bounds_()
+        {
+                let mut emulator = Emulator::new(10, 3);
+                emulator.write_all(b"1\r\n2\r\n3\x1b[3S\x1b[9T").unwrap();
+                assert_eq!("", emulator.text());
+                let mut emulator = Emulator::new(300, 250);
+                emulator.write_all(b"x\x1b[2J\x1b[250;300Hx\x1b[1J")
+                        .unwrap();
+                assert_eq!("", emulator.text());
+                let mut emulator = Emulator::new(0, 0);
+                emulator.write_all(b"a\nb\x1b[r\x1b[2J\x1b[S\x1b[T\t")
+                        .unwrap();
+                let mut emulator = Emulator::new(u16::MAX, 1);
+                emulator.write_all(b"\x1b[65535G\t\x1b[K").unwrap();
+        }
+
+        #[test]
+        fn wide_()
+        {
+                let mut emulator = Emulator::new(6, 2);
+                emulator.write_all("日x\x1b[1;1Ha\r\n日x\x1b[2;2Hb".as_bytes())
+                        .unwrap();
+                assert_eq!("a x\n bx", emulator.text());
+        }
+}
@@ -1,6 +1,121 @@
-use std::result::Result as Result_;
+use libc::ENOTTY;
+use std::{
+        fmt::{
+                Display,
+                Formatter,
+        },
+        io::{
+                self,
+                ErrorKind,
+        },
+        result::Result as Result_,
+};
 
 pub type Result<T> = Result_<T, Error>;
 
+/// Error of ruterm.
+///
+/// Underlying [`io::Error`] is available through [`std::error::Error::source()`].
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         error::Error,
+///         raw::Termios,
+/// };
+/// use std::io;
+///
+/// match Termios::new(io::stdin()) {
+///         Ok(termios) => termios.raw().unwrap(),
+///         Err(Error::NotATty) => eprintln!("stdin is redirected"),
+///         Err(error) => panic!("{error}"),
+/// }
+/// ```
 #[derive(Debug)]
-pub struct Error(pub &'static str);
+pub enum Error
+{
+        /// File descriptor doesn't refer to a terminal.
+        NotATty,
+        /// Reading, writing or a system call failed.
+        Io(io::Error),
+        /// Getting or setting termios settings failed.
+        Termios(io::Error),
+        /// Terminal didn't reply in time. Contains name of the query.
+        Timeout(&'static str),
+        /// Terminal sent invalid reply. Contains name of the reply.
+        Parse(&'static str),
+        /// Terminal or environment doesn't provide the feature.
+        Unsupported(&'static str),
+}
+
+impl Display for Error
+{
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+        {
+                match self {
+                        Self::NotATty => write!(f, "not a terminal"),
+                        Self::Io(_) => write!(f, "input/output error"),
+                        Self::Termios(_) => write!(f, "failed to access termios settings"),
+                        Self::Timeout(query) => write!(f, "{} timed out", query),
+                        Self::Parse(reply) => write!(f, "invalid {}", reply),
+                        Self::Unsupported(feature) => write!(f, "{} is not supported", feature),
+                }
+        }
+}
+
+impl std::error::Error for Error
+{
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+        {
+                match self {
+                        Self::Io(error) | Self::Termios(error) => Some(error),
+                        _ => None,
+                }
+        }
+}
+
+impl From<io::Error> for Error
+{
+        fn from(error: io::Error) -> Self
+        {
+                match error.raw_os_error() {
+                        Some(ENOTTY) => Self::NotATty,
+                        _ => Self::Io(error),
+                }
+        }
+}
+
+impl From<Error> for io::Error
+{
+        fn from(error: Error) -> Self
+        {
+                match error {
+                        Error::Io(error) | Error::Termios(error) => error,
+                        Error::NotATty => io::Error::from_raw_os_error(ENOTTY),
+                        Error::Timeout(_) => io::Error::new(ErrorKind::TimedOut, error),
+                        Error::Parse(_) => io::Error::new(ErrorKind::InvalidData, error),
+                        Error::Unsupported(_) => io::Error::new(ErrorKind::Unsupported, error),
+                }
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+        use std::error::Error as _;
+
+        #[test]
+        fn from_()
+        {
+                let error = Error::from(io::Error::from_raw_os_error(ENOTTY));
+                assert!(matches!(error, Error::NotATty));
+                let error = Error::from(io::Error::from(ErrorKind::BrokenPipe));
+                assert_eq!("input/output error", error.to_string());
+                assert!(error.source().is_some());
+                let error = io::Error::from(Error::Timeout("cursor position query"));
+                assert_eq!(ErrorKind::TimedOut, error.kind());
+                assert_eq!("cursor position query timed out", error.to_string());
+        }
+}
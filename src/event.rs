@@ -0,0 +1,987 @@
+use crate::{
+        error::Result,
+        tio::{
+                self,
+                write_with_output,
+        },
+};
+#[cfg(feature = "signal")]
+use crate::{
+        signal::Signals,
+        tio::Fd,
+};
+#[cfg(feature = "signal")]
+use libc::{
+        pollfd,
+        POLLIN,
+        SIGCONT,
+        SIGWINCH,
+};
+use std::{
+        fmt::{
+                Display,
+                Formatter,
+        },
+        io::{
+                self,
+                Read,
+                Write,
+        },
+        result::Result as Result_,
+        sync::atomic::{
+                AtomicBool,
+                AtomicU16,
+                Ordering,
+        },
+};
+#[cfg(feature = "signal")]
+use std::{
+        os::fd::AsRawFd,
+        time::Duration,
+};
+
+/// Key modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers
+{
+        pub shift: bool,
+        pub alt: bool,
+        pub ctrl: bool,
+}
+
+impl Modifiers
+{
+        pub const NONE: Self = Self {
+                shift: false,
+                alt: false,
+                ctrl: false,
+        };
+
+        /// Decodes xterm modifier parameter (`1 + bitmask`).
+        fn from_parameter(parameter: u16) -> Self
+        {
+                let mask = parameter.saturating_sub(1);
+                Self {
+                        shift: mask & 1 != 0,
+                        alt: mask & 2 != 0,
+                        ctrl: mask & 4 != 0,
+                }
+        }
+}
+
+/// Key code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCode
+{
+        Char(char),
+        Enter,
+        Tab,
+        BackTab,
+        Backspace,
+        Esc,
+        Left,
+        Right,
+        Up,
+        Down,
+        Home,
+        End,
+        PageUp,
+        PageDown,
+        Insert,
+        Delete,
+        /// Function key, from `F(1)` to `F(12)`.
+        F(u8),
+}
+
+/// Key press with modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent
+{
+        pub code: KeyCode,
+        pub modifiers: Modifiers,
+}
+
+impl KeyEvent
+{
+        pub fn new(code: KeyCode, modifiers: Modifiers) -> Self
+        {
+                Self { code, modifiers }
+        }
+
+        fn with_alt(mut self) -> Self
+        {
+                self.modifiers.alt = true;
+                self
+        }
+}
+
+impl From<KeyCode> for KeyEvent
+{
+        fn from(code: KeyCode) -> Self
+        {
+                Self::new(code, Modifiers::NONE)
+        }
+}
+
+/// Mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton
+{
+        Left,
+        Middle,
+        Right,
+        WheelUp,
+        WheelDown,
+        WheelLeft,
+        WheelRight,
+}
+
+/// Kind of mouse event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseKind
+{
+        Press,
+        Release,
+        /// Motion with a button held. Reported in [`Tracking::Drag`] and [`Tracking::Motion`].
+        Drag,
+        /// Motion without buttons. Reported in [`Tracking::Motion`].
+        Move,
+        Scroll,
+}
+
+/// Mouse report.
+///
+/// `column` and `row` start from 1, same as in [`crate::cursor::set()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent
+{
+        /// `None` for motion without buttons and for legacy releases, which don't tell the button.
+        pub button: Option<MouseButton>,
+        pub kind: MouseKind,
+        pub column: u16,
+        pub row: u16,
+        pub modifiers: Modifiers,
+}
+
+impl MouseEvent
+{
+        /// Decodes button byte of SGR and X10 reports.
+        fn new(code: u16, column: u16, row: u16, release: bool) -> Self
+        {
+                let modifiers = Modifiers {
+                        shift: code & 4 != 0,
+                        alt: code & 8 != 0,
+                        ctrl: code & 16 != 0,
+                };
+                let motion = code & 32 != 0;
+                let button = match (code & 64 != 0, code & 3) {
+                        (false, 0) => Some(MouseButton::Left),
+                        (false, 1) => Some(MouseButton::Middle),
+                        (false, 2) => Some(MouseButton::Right),
+                        (false, _) => None,
+                        (true, 0) => Some(MouseButton::WheelUp),
+                        (true, 1) => Some(MouseButton::WheelDown),
+                        (true, 2) => Some(MouseButton::WheelLeft),
+                        (true, _) => Some(MouseButton::WheelRight),
+                };
+                let kind = match button {
+                        _ if code & 64 != 0 => MouseKind::Scroll,
+                        Some(_) if motion => MouseKind::Drag,
+                        None if motion => MouseKind::Move,
+                        None => MouseKind::Release,
+                        Some(_) if release => MouseKind::Release,
+                        Some(_) => MouseKind::Press,
+                };
+                Self {
+                        button,
+                        kind,
+                        column,
+                        row,
+                        modifiers,
+                }
+        }
+}
+
+/// Input event. Returned by [`read()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event
+{
+        Key(KeyEvent),
+        Mouse(MouseEvent),
+        /// New terminal size (width, height). Reported by [`wait()`].
+        Resize(u16, u16),
+        /// Pasted text, with line breaks converted to `\n`. Reported when bracketed paste is
+        /// enabled, see [`enable_bracketed_paste()`].
+        Paste(String),
+        /// Terminal window gained focus. Reported when focus reporting is enabled, see
+        /// [`enable_focus_reporting()`].
+        FocusGained,
+        /// Terminal window lost focus.
+        FocusLost,
+        /// Process was resumed after being stopped, e.g. by [`crate::raw::Termios::suspend()`].
+        /// Contains terminal size (width, height), which may have changed meanwhile. Screen content
+        /// may be lost, so everything should be drawn again. Reported by [`wait()`].
+        Redraw(u16, u16),
+}
+
+const ESCAPE: u8 = 0x1b;
+
+fn ctrl(c: char) -> KeyEvent
+{
+        KeyEvent::new(
+                KeyCode::Char(c),
+                Modifiers {
+                        ctrl: true,
+                        ..Modifiers::NONE
+                },
+        )
+}
+
+/// Fails with the byte that broke a truncated UTF-8 sequence. The sequence is dropped.
+fn key(byte: u8, input: &mut impl Read) -> Result_<KeyEvent, u8>
+{
+        Ok(match byte {
+                b'\r' | b'\n' => KeyCode::Enter.into(),
+                b'\t' => KeyCode::Tab.into(),
+                0x7f | 0x08 => KeyCode::Backspace.into(),
+                ESCAPE => KeyCode::Esc.into(),
+                0x00 => ctrl(' '),
+                0x01..=0x1a => ctrl((byte - 0x01 + b'a') as char),
+                0x1c..=0x1f => ctrl((byte - 0x1c + b'4') as char),
+                0x20..=0x7e => KeyCode::Char(byte as char).into(),
+                0x80..=0xff => match tio::utf8_with_input(byte, input) {
+                        Err(Some(next)) => return Err(next),
+                        c => KeyCode::Char(c.unwrap_or(char::REPLACEMENT_CHARACTER)).into(),
+                },
+        })
+}
+
+fn tilde(number: u16) -> Option<KeyCode>
+{
+        Some(match number {
+                1 | 7 => KeyCode::Home,
+                2 => KeyCode::Insert,
+                3 => KeyCode::Delete,
+                4 | 8 => KeyCode::End,
+                5 => KeyCode::PageUp,
+                6 => KeyCode::PageDown,
+                11..=15 => KeyCode::F((number - 10) as u8),
+                17..=21 => KeyCode::F((number - 11) as u8),
+                23 | 24 => KeyCode::F((number - 12) as u8),
+                _ => return None,
+        })
+}
+
+/// Final byte shared by CSI and SS3 forms.
+fn final_key(byte: u8) -> Option<KeyCode>
+{
+        Some(match byte {
+                b'A' => KeyCode::Up,
+                b'B' => KeyCode::Down,
+                b'C' => KeyCode::Right,
+                b'D' => KeyCode::Left,
+                b'H' => KeyCode::Home,
+                b'F' => KeyCode::End,
+                b'P' => KeyCode::F(1),
+                b'Q' => KeyCode::F(2),
+                b'R' => KeyCode::F(3),
+                b'S' => KeyCode::F(4),
+                _ => return None,
+        })
+}
+
+/// Parses parameters separated by `;`. Returns `None` if there are other bytes than digits.
+fn parameters(bytes: &[u8]) -> Option<Vec<u16>>
+{
+        if !bytes.iter().all(|b| b.is_ascii_digit() || *b == b';') {
+                return None;
+        }
+        let parameters = bytes
+                .split(|b| *b == b';')
+                .map(|p| {
+                        p.iter().fold(0u16, |n, d| {
+                                n.saturating_mul(10).saturating_add((d - b'0') as u16)
+                        })
+                })
+                .collect();
+        Some(parameters)
+}
+
+/// Decodes `CSI < b ; x ; y M/m`.
+fn sgr_mouse(bytes: &[u8], end: u8) -> Option<Event>
+{
+        let parameters = parameters(bytes)?;
+        match (parameters.as_slice(), end) {
+                ([code, column, row], b'M' | b'm') => Some(Event::Mouse(MouseEvent::new(
+                        *code,
+                        *column,
+                        *row,
+                        end == b'm',
+                ))),
+                _ => None,
+        }
+}
+
+/// Decodes `CSI M b x y`, where each value is a byte shifted by 32.
+fn x10_mouse(input: &mut impl Read) -> Option<Event>
+{
+        let mut next = || tio::read_with_input(input).map(|b| b.saturating_sub(32) as u16);
+        let (code, column, row) = (next()?, next()?, next()?);
+        Some(Event::Mouse(MouseEvent::new(code, column, row, false)))
+}
+
+/// Maximum length of pasted text in bytes. The rest of the paste is discarded.
+pub const PASTE_LIMIT: usize = 1 << 20;
+
+/// Number of empty reads in a row which end the paste. In raw mode each of them takes `VTIME`.
+const PASTE_EMPTY_READS: u8 = 50;
+
+/// Reads pasted text until `CSI 201 ~`.
+///
+/// Empty reads (e.g. `VTIME` expiring in raw mode) don't end the paste, unless there are
+/// [`PASTE_EMPTY_READS`] of them in a row. Text is cut short if reading fails.
+fn paste(input: &mut impl Read) -> Option<Event>
+{
+        const END: &[u8] = b"\x1b[201~";
+        let mut bytes = Vec::new();
+        let mut keep = |part: &[u8]| {
+                let room = PASTE_LIMIT - bytes.len();
+                bytes.extend_from_slice(&part[..part.len().min(room)]);
+        };
+        let mut buffer = [0; 1];
+        // length of the terminator prefix read so far
+        let mut matched = 0;
+        let mut empty = 0;
+        while matched < END.len() {
+                match input.read(&mut buffer) {
+                        Ok(0) if empty + 1 < PASTE_EMPTY_READS => empty += 1,
+                        Ok(0) => break,
+                        Ok(_) => {
+                                empty = 0;
+                                let byte = buffer[0];
+                                if byte == END[matched] {
+                                        matched += 1;
+                                        continue;
+                                }
+                                keep(&END[..matched]);
+                                matched = (byte == END[0]) as usize;
+                                if matched == 0 {
+                                        keep(&[byte]);
+                                }
+                        }
+                        Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => break,
+                }
+        }
+        if matched < END.len() {
+                // input ended inside of the terminator
+                keep(&END[..matched]);
+        }
+        let text = String::from_utf8_lossy(&bytes)
+                .replace("\r\n", "\n")
+                .replace('\r', "\n");
+        Some(Event::Paste(text))
+}
+
+fn csi(input: &mut impl Read) -> Option<Event>
+{
+        let mut bytes = Vec::new();
+        let end = loop {
+                match tio::read_with_input(input)? {
+                        byte @ 0x40..=0x7e => break byte,
+                        byte => bytes.push(byte),
+                }
+        };
+        if let Some(b'<') = bytes.first() {
+                return sgr_mouse(&bytes[1..], end);
+        }
+        if bytes.is_empty() {
+                match end {
+                        b'M' => return x10_mouse(input),
+                        b'I' => return Some(Event::FocusGained),
+                        b'O' => return Some(Event::FocusLost),
+                        _ => {}
+                }
+        }
+        let parameters = parameters(&bytes)?;
+        let modifiers = Modifiers::from_parameter(parameters.get(1).copied().unwrap_or(1));
+        let code = match end {
+                b'~' if parameters[0] == 200 => return paste(input),
+                b'~' => tilde(parameters[0])?,
+                b'Z' => KeyCode::BackTab,
+                _ => final_key(end)?,
+        };
+        Some(Event::Key(KeyEvent::new(code, modifiers)))
+}
+
+fn ss3(input: &mut impl Read) -> Option<Event>
+{
+        let code = final_key(tio::read_with_input(input)?)?;
+        Some(Event::Key(code.into()))
+}
+
+/// Reads one event from `input`. Same as [`read()`].
+pub fn read_with_input(input: &mut impl Read) -> Option<Event>
+{
+        let mut byte = tio::read_with_input(input)?;
+        loop {
+                let key = match byte {
+                        // A lone escape is told apart from a sequence by the read timeout (`VTIME`)
+                        // of raw mode.
+                        ESCAPE => match tio::read_with_input(input) {
+                                None => Ok(KeyCode::Esc.into()),
+                                Some(b'[') => return csi(input),
+                                Some(b'O') => return ss3(input),
+                                Some(byte) => key(byte, input).map(KeyEvent::with_alt),
+                        },
+                        _ => key(byte, input),
+                };
+                match key {
+                        Ok(key) => return Some(Event::Key(key)),
+                        // byte that broke UTF-8 sequence starts the next event
+                        Err(next) => byte = next,
+                }
+        }
+}
+
+/// Reads one event from stdin.
+///
+/// Decodes escape sequences sent by arrows, Home/End, PageUp/PageDown, Insert/Delete, F1-F12 and
+/// Alt/Ctrl combinations, as well as mouse reports (see [`enable_mouse()`]), pasted text (see
+/// [`enable_bracketed_paste()`]) and focus changes (see [`enable_focus_reporting()`]). Should be
+/// used in raw mode.
+///
+/// Returns `None` when there is no input or the sequence is not recognized.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::event::{
+///         self,
+///         Event,
+///         KeyCode,
+///         KeyEvent,
+/// };
+///
+/// match event::read() {
+///         Some(Event::Key(KeyEvent {
+///                 code: KeyCode::Up,
+///                 ..
+///         })) => println!("up"),
+///         Some(Event::Key(key)) => println!("{key:?}"),
+///         _ => {}
+/// }
+/// ```
+pub fn read() -> Option<Event>
+{
+        read_with_input(&mut io::stdin())
+}
+
+/// Waits for an event from `input` or `signals`. Same as [`wait()`].
+#[cfg(feature = "signal")]
+pub fn wait_with_input(
+        input: &impl AsRawFd,
+        signals: &Signals,
+        timeout: Duration,
+) -> Result<Option<Event>>
+{
+        let mut fds = [input.as_raw_fd(), signals.as_raw_fd()].map(|fd| pollfd {
+                fd,
+                events: POLLIN,
+                revents: 0,
+        });
+        if !tio::poll_fds(&mut fds, timeout)? {
+                return Ok(None);
+        }
+        if fds[1].revents & POLLIN != 0 {
+                let pending = signals.pending();
+                // resize while the process was stopped is reported by the redraw
+                if pending.contains(&SIGCONT) {
+                        let (width, height) = crate::size()?;
+                        return Ok(Some(Event::Redraw(width, height)));
+                }
+                if pending.contains(&SIGWINCH) {
+                        let (width, height) = crate::size()?;
+                        return Ok(Some(Event::Resize(width, height)));
+                }
+        }
+        if fds[0].revents & POLLIN != 0 {
+                return Ok(read_with_input(&mut Fd(input.as_raw_fd())));
+        }
+        Ok(None)
+}
+
+/// Waits for an event from stdin or `signals`, at most `timeout`.
+///
+/// Same as [`read()`], but also reports [`Event::Resize`] when the terminal window is resized and
+/// [`Event::Redraw`] when the process is resumed.
+///
+/// Returns `None` if `timeout` expires or the input is not recognized.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         event::{
+///                 self,
+///                 Event,
+///         },
+///         signal::Signals,
+/// };
+/// use std::time::Duration;
+///
+/// let signals = Signals::new().unwrap();
+/// loop {
+///         match event::wait(&signals, Duration::from_millis(100)).unwrap() {
+///                 Some(Event::Resize(width, height)) => println!("{width}x{height}"),
+///                 Some(event) => println!("{event:?}"),
+///                 None => {}
+///         }
+/// }
+/// ```
+///
+/// # Note
+///
+/// Reads the file descriptor directly, bypassing the buffer of [`io::stdin()`].
+#[cfg(feature = "signal")]
+pub fn wait(signals: &Signals, timeout: Duration) -> Result<Option<Event>>
+{
+        wait_with_input(&io::stdin(), signals, timeout)
+}
+
+/// Mouse tracking mode. Used in [`enable_mouse()`].
+pub enum Tracking
+{
+        /// Button presses and releases.
+        Click,
+        /// Same as [`Tracking::Click`], plus motion with a button held.
+        Drag,
+        /// Same as [`Tracking::Drag`], plus motion without buttons.
+        Motion,
+}
+
+impl Tracking
+{
+        fn code(&self) -> u16
+        {
+                match self {
+                        Self::Click => 1000,
+                        Self::Drag => 1002,
+                        Self::Motion => 1003,
+                }
+        }
+}
+
+impl Display for Tracking
+{
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+        {
+                write!(f, "{}", self.code())
+        }
+}
+
+/// Mouse tracking mode enabled last, 0 if disabled. Restored by [`resume_reporting_with_output()`].
+static MOUSE: AtomicU16 = AtomicU16::new(0);
+static PASTE: AtomicBool = AtomicBool::new(false);
+static FOCUS: AtomicBool = AtomicBool::new(false);
+
+/// Disables all reporting modes, but keeps track of the enabled ones. Used on suspend.
+pub(crate) fn pause_reporting_with_output(output: &mut dyn Write) -> Result<()>
+{
+        write_with_output(output, "\x1b[?1006l\x1b[?1003l\x1b[?1002l\x1b[?1000l")?;
+        write_with_output(output, "\x1b[?2004l\x1b[?1004l")?;
+        Ok(())
+}
+
+/// Enables reporting modes disabled by [`pause_reporting_with_output()`] again.
+pub(crate) fn resume_reporting_with_output(output: &mut dyn Write) -> Result<()>
+{
+        match MOUSE.load(Ordering::Relaxed) {
+                0 => {}
+                code => {
+                        write_with_output(output, format!("\x1b[?{}h\x1b[?1006h", code))?;
+                }
+        }
+        if PASTE.load(Ordering::Relaxed) {
+                write_with_output(output, "\x1b[?2004h")?;
+        }
+        if FOCUS.load(Ordering::Relaxed) {
+                write_with_output(output, "\x1b[?1004h")?;
+        }
+        Ok(())
+}
+
+/// Enables mouse reporting. Writes to `output`. Same as [`enable_mouse()`].
+pub fn enable_mouse_with_output(output: &mut dyn Write, tracking: Tracking) -> Result<usize>
+{
+        MOUSE.store(tracking.code(), Ordering::Relaxed);
+        write_with_output(output, format!("\x1b[?{}h\x1b[?1006h", tracking))
+}
+
+/// Enables mouse reporting. Writes to stdout.
+///
+/// Reports are requested in SGR (1006) format. Terminals which don't support it fall back to
+/// legacy X10 format. Both are decoded by [`read()`].
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::event::{
+///         self,
+///         Event,
+///         Tracking,
+/// };
+///
+/// event::enable_mouse(Tracking::Click).unwrap();
+/// if let Some(Event::Mouse(mouse)) = event::read() {
+///         println!("{} {}", mouse.column, mouse.row);
+/// }
+/// event::disable_mouse().unwrap();
+/// ```
+pub fn enable_mouse(tracking: Tracking) -> Result<usize>
+{
+        enable_mouse_with_output(&mut io::stdout(), tracking)
+}
+
+/// Disables mouse reporting. Writes to `output`. Same as [`disable_mouse()`].
+pub fn disable_mouse_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        MOUSE.store(0, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?1006l\x1b[?1003l\x1b[?1002l\x1b[?1000l")
+}
+
+/// Disables mouse reporting in all tracking modes. Writes to stdout.
+pub fn disable_mouse() -> Result<usize>
+{
+        disable_mouse_with_output(&mut io::stdout())
+}
+
+/// Enables bracketed paste. Writes to `output`. Same as [`enable_bracketed_paste()`].
+pub fn enable_bracketed_paste_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        PASTE.store(true, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?2004h")
+}
+
+/// Enables bracketed paste (mode 2004). Writes to stdout.
+///
+/// Terminal wraps pasted text in `CSI 200 ~` and `CSI 201 ~`, which [`read()`] decodes into a
+/// single [`Event::Paste`], so pasted line breaks and escape sequences are not taken for keys.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::event::{
+///         self,
+///         Event,
+/// };
+///
+/// event::enable_bracketed_paste().unwrap();
+/// if let Some(Event::Paste(text)) = event::read() {
+///         println!("pasted {} lines", text.lines().count());
+/// }
+/// event::disable_bracketed_paste().unwrap();
+/// ```
+pub fn enable_bracketed_paste() -> Result<usize>
+{
+        enable_bracketed_paste_with_output(&mut io::stdout())
+}
+
+/// Disables bracketed paste. Writes to `output`. Same as [`disable_bracketed_paste()`].
+pub fn disable_bracketed_paste_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        PASTE.store(false, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?2004l")
+}
+
+/// Disables bracketed paste. Writes to stdout.
+pub fn disable_bracketed_paste() -> Result<usize>
+{
+        disable_bracketed_paste_with_output(&mut io::stdout())
+}
+
+/// Enables focus reporting. Writes to `output`. Same as [`enable_focus_reporting()`].
+pub fn enable_focus_reporting_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        FOCUS.store(true, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?1004h")
+}
+
+/// Enables focus reporting (mode 1004). Writes to stdout.
+///
+/// Terminal sends `CSI I` and `CSI O` when its window gains and loses focus, which [`read()`]
+/// decodes into [`Event::FocusGained`] and [`Event::FocusLost`].
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::event::{
+///         self,
+///         Event,
+/// };
+///
+/// event::enable_focus_reporting().unwrap();
+/// match event::read() {
+///         Some(Event::FocusLost) => println!("paused"),
+///         Some(Event::FocusGained) => println!("resumed"),
+///         _ => {}
+/// }
+/// event::disable_focus_reporting().unwrap();
+/// ```
+pub fn enable_focus_reporting() -> Result<usize>
+{
+        enable_focus_reporting_with_output(&mut io::stdout())
+}
+
+/// Disables focus reporting. Writes to `output`. Same as [`disable_focus_reporting()`].
+pub fn disable_focus_reporting_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        FOCUS.store(false, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?1004l")
+}
+
+/// Disables focus reporting. Writes to stdout.
+pub fn disable_focus_reporting() -> Result<usize>
+{
+        disable_focus_reporting_with_output(&mut io::stdout())
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        const CTRL: Modifiers = Modifiers {
+                ctrl: true,
+                ..Modifiers::NONE
+        };
+        const ALT: Modifiers = Modifiers {
+                alt: true,
+                ..Modifiers::NONE
+        };
+        const SHIFT: Modifiers = Modifiers {
+                shift: true,
+                ..Modifiers::NONE
+        };
+
+        fn decode(bytes: &[u8]) -> Vec<Event>
+        {
+                let mut input = bytes;
+                let mut events = Vec::new();
+                while !input.is_empty() {
+                        if let Some(event) = read_with_input(&mut input) {
+                                events.push(event);
+                        }
+                }
+                events
+        }
+
+        fn key(code: KeyCode, modifiers: Modifiers) -> Event
+        {
+                Event::Key(KeyEvent::new(code, modifiers))
+        }
+
+        #[test]
+        fn plain_()
+        {
+                assert_eq!(
+                        vec![
+                                key(KeyCode::Char('h'), Modifiers::NONE),
+                                key(KeyCode::Enter, Modifiers::NONE),
+                                key(KeyCode::Char('c'), CTRL),
+                                key(KeyCode::Char('x'), ALT),
+                                key(KeyCode::Esc, Modifiers::NONE),
+                        ],
+                        decode(b"h\r\x03\x1bx\x1b")
+                );
+        }
+
+        #[test]
+        fn csi_()
+        {
+                assert_eq!(
+                        vec![
+                                key(KeyCode::Up, Modifiers::NONE),
+                                key(KeyCode::Delete, Modifiers::NONE),
+                                key(KeyCode::F(5), Modifiers::NONE),
+                                key(KeyCode::Right, CTRL),
+                                key(KeyCode::PageDown, SHIFT),
+                        ],
+                        decode(b"\x1b[A\x1b[3~\x1b[15~\x1b[1;5C\x1b[6;2~")
+                );
+        }
+
+        #[test]
+        fn ss3_()
+        {
+                assert_eq!(
+                        vec![
+                                key(KeyCode::F(1), Modifiers::NONE),
+                                key(KeyCode::Home, Modifiers::NONE)
+                        ],
+                        decode(b"\x1bOP\x1bOH")
+                );
+        }
+
+        #[test]
+        fn mouse_()
+        {
+                let mouse = |button, kind, column, row| {
+                        Event::Mouse(MouseEvent {
+                                button,
+                                kind,
+                                column,
+                                row,
+                                modifiers: Modifiers::NONE,
+                        })
+                };
+                assert_eq!(
+                        vec![
+                                mouse(Some(MouseButton::Left), MouseKind::Press, 10, 5),
+                                mouse(Some(MouseButton::Left), MouseKind::Release, 10, 5),
+                                mouse(Some(MouseButton::Right), MouseKind::Drag, 300, 2),
+                                mouse(Some(MouseButton::WheelDown), MouseKind::Scroll, 1, 1),
+                                mouse(Some(MouseButton::Middle), MouseKind::Press, 3, 4),
+                                mouse(None, MouseKind::Release, 3, 4),
+                        ],
+                        decode(concat!(
+                                "\x1b[<0;10;5M",
+                                "\x1b[<0;10;5m",
+                                "\x1b[<34;300;2M",
+                                "\x1b[<65;1;1M",
+                                "\x1b[M!#$",
+                                "\x1b[M##$"
+                        )
+                        .as_bytes())
+                );
+        }
+
+        #[test]
+        fn utf8_()
+        {
+                let mut bytes = "é\x1bж🦀".as_bytes().to_vec();
+                bytes.extend_from_slice(b"\xe2\x82"); // truncated
+                assert_eq!(
+                        vec![
+                                key(KeyCode::Char('é'), Modifiers::NONE),
+                                key(KeyCode::Char('ж'), ALT),
+                                key(KeyCode::Char('🦀'), Modifiers::NONE),
+                                key(KeyCode::Char(char::REPLACEMENT_CHARACTER), Modifiers::NONE),
+                        ],
+                        decode(&bytes)
+                );
+                assert_eq!(
+                        vec![
+                                key(KeyCode::Up, Modifiers::NONE),
+                                key(KeyCode::Char('a'), Modifiers::NONE),
+                        ],
+                        decode(b"\xc3\x1b[A\xe2\x82a")
+                );
+        }
+
+        #[test]
+        fn paste_()
+        {
+                assert_eq!(
+                        vec![
+                                Event::Paste("a\x1b[Ab\nc\nd".to_string()),
+                                key(KeyCode::Char('x'), Modifiers::NONE),
+                        ],
+                        decode(b"\x1b[200~a\x1b[Ab\r\nc\rd\x1b[201~x")
+                );
+                assert_eq!(
+                        vec![Event::Paste("ab\x1b[20".to_string())],
+                        decode(b"\x1b[200~ab\x1b[20")
+                );
+        }
+
+        #[test]
+        fn paste_limit_()
+        {
+                let mut bytes = b"\x1b[200~".to_vec();
+                bytes.resize(bytes.len() + PASTE_LIMIT + 1, b'a');
+                bytes.extend_from_slice(b"\x1b[201~x");
+                assert_eq!(
+                        vec![
+                                Event::Paste("a".repeat(PASTE_LIMIT)),
+                                key(KeyCode::Char('x'), Modifiers::NONE),
+                        ],
+                        decode(&bytes)
+                );
+        }
+
+        /// Returns an empty read after each chunk, like raw mode does when input stalls.
+        struct Chunked<'a>
+        {
+                chunks: Vec<&'a [u8]>,
+        }
+
+        impl Read for Chunked<'_>
+        {
+                fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+                {
+                        match self.chunks.first_mut() {
+                                Some([]) => {
+                                        self.chunks.remove(0);
+                                        Ok(0)
+                                }
+                                Some(chunk) => chunk.read(buffer),
+                                None => Ok(0),
+                        }
+                }
+        }
+
+        #[test]
+        fn paste_chunked_()
+        {
+                let mut input = Chunked {
+                        chunks: vec![b"\x1b[200~hel", b"lo\x1b[2", b"01~"],
+                };
+                assert_eq!(
+                        Some(Event::Paste("hello".to_string())),
+                        read_with_input(&mut input)
+                );
+        }
+
+        #[test]
+        fn focus_()
+        {
+                assert_eq!(
+                        vec![
+                                Event::FocusLost,
+                                key(KeyCode::Up, Modifiers::NONE),
+                                Event::FocusGained,
+                        ],
+                        decode(b"\x1b[O\x1b[A\x1b[I")
+                );
+        }
+
+        #[test]
+        fn malformed_()
+        {
+                assert_eq!(
+                        vec![
+                                // `[` ends the broken sequence, so `A` is read as a key
+                                key(KeyCode::Char('A'), Modifiers::NONE),
+                                key(KeyCode::Char('x'), Modifiers::NONE),
+                        ],
+                        decode(b"\x1b[< 1;2M\x1b[<\x1b[Ax\x1b[<1;2;3;4M")
+                );
+        }
+
+        #[test]
+        fn resume_reporting_()
+        {
+                let mut output = Vec::new();
+                enable_mouse_with_output(&mut output, Tracking::Drag).unwrap();
+                enable_focus_reporting_with_output(&mut output).unwrap();
+                output.clear();
+                resume_reporting_with_output(&mut output).unwrap();
+                assert_eq!(b"\x1b[?1002h\x1b[?1006h\x1b[?1004h".to_vec(), output);
+                disable_mouse_with_output(&mut output).unwrap();
+                disable_focus_reporting_with_output(&mut output).unwrap();
+                output.clear();
+                resume_reporting_with_output(&mut output).unwrap();
+                assert!(output.is_empty());
+        }
+}
@@ -1,13 +1,40 @@
+pub mod ansi;
+#[cfg(feature = "buffer")]
+pub mod buffer;
+pub mod clear;
 pub mod cursor;
+#[cfg(any(feature = "emulator", all(test, feature = "buffer")))]
+pub mod emulator;
 pub mod error;
+#[cfg(feature = "event")]
+pub mod event;
+pub mod panic;
 pub mod prelude;
 pub mod raw;
+pub mod screen;
+pub mod scroll;
+#[cfg(any(feature = "snapshot", all(test, feature = "buffer")))]
+pub mod snapshot;
+#[cfg(feature = "signal")]
+pub mod signal;
 #[cfg(feature = "size")]
 mod size;
+#[cfg(feature = "buffer")]
+pub mod terminal;
 pub mod tio;
+pub mod tty;
 #[cfg(feature = "size")]
-pub use size::size;
+pub use size::{
+        query_size,
+        query_size_with_io,
+        size,
+        size_of,
+        size_with_output,
+        WindowSize,
+        SIZE_TIMEOUT,
+};
 #[cfg(feature = "render")]
 pub mod render;
 #[cfg(feature = "view")]
 pub mod view;
+pub mod writer;
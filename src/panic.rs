@@ -0,0 +1,76 @@
+#[cfg(feature = "event")]
+use crate::event::{
+        disable_bracketed_paste_with_output,
+        disable_focus_reporting_with_output,
+        disable_mouse_with_output,
+};
+use crate::{
+        cursor::show_with_output,
+        error::Result,
+        raw::termios,
+        screen::{
+                leave_alternate_screen_with_output,
+                ALTERNATE,
+        },
+        tio::flush_with_output,
+};
+use std::{
+        io,
+        os::fd::AsRawFd,
+        panic,
+        sync::atomic::Ordering,
+};
+use termios::{
+        tcsetattr,
+        TCSAFLUSH,
+};
+
+/// Installs panic hook which restores the terminal before printing the panic message.
+///
+/// Restores current termios settings of stdin, leaves the alternate screen if it was entered,
+/// disables mouse reporting, bracketed paste and focus reporting and shows the cursor. Then calls
+/// the previous hook, so the message is printed with output post-processing enabled.
+///
+/// Should be called before enabling raw mode.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         error::Result,
+///         in_raw,
+///         panic,
+/// };
+///
+/// fn main() -> Result<()>
+/// {
+///         panic::install_hook()?;
+///         in_raw!({
+///                 panic!("terminal is restored");
+///         });
+///         Ok(())
+/// }
+/// ```
+pub fn install_hook() -> Result<()>
+{
+        let original = termios(&io::stdin())?;
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+                let _ = tcsetattr(io::stdin().as_raw_fd(), TCSAFLUSH, &original);
+                let mut stdout = io::stdout();
+                #[cfg(feature = "event")]
+                {
+                        let _ = disable_mouse_with_output(&mut stdout);
+                        let _ = disable_bracketed_paste_with_output(&mut stdout);
+                        let _ = disable_focus_reporting_with_output(&mut stdout);
+                }
+                // leaving also restores the saved cursor on some terminals
+                if ALTERNATE.load(Ordering::Relaxed) {
+                        let _ = leave_alternate_screen_with_output(&mut stdout);
+                }
+                let _ = show_with_output(&mut stdout);
+                let _ = flush_with_output(&mut stdout);
+                previous(info);
+        }));
+        Ok(())
+}
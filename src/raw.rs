@@ -2,8 +2,22 @@ use crate::error::{
         Error,
         Result,
 };
-use std::os::fd::AsRawFd;
+#[cfg(feature = "event")]
+use crate::{
+        event::{
+                pause_reporting_with_output,
+                resume_reporting_with_output,
+        },
+        tio::Fd,
+};
+use libc::SIGTSTP;
+use std::{
+        io,
+        os::fd::AsRawFd,
+};
 use termios::{
+        cc_t,
+        tcflag_t,
         tcsetattr,
         Termios as Termios_,
         CS8,
@@ -19,9 +33,12 @@ use termios::{
         VTIME,
 };
 
-fn termios<D: AsRawFd>(fd: &D) -> Result<Termios_>
+pub(crate) fn termios<D: AsRawFd>(fd: &D) -> Result<Termios_>
 {
-        Termios_::from_fd(fd.as_raw_fd()).map_err(|_| Error("Invalid file descriptor."))
+        Termios_::from_fd(fd.as_raw_fd()).map_err(|error| match Error::from(error) {
+                Error::Io(error) => Error::Termios(error),
+                error => error,
+        })
 }
 
 fn raw(mut termios: Termios_) -> Termios_
@@ -35,9 +52,155 @@ fn raw(mut termios: Termios_) -> Termios_
         termios
 }
 
+fn cbreak(mut termios: Termios_) -> Termios_
+{
+        termios.c_lflag &= !(ECHO | ICANON);
+        termios.c_cc[VTIME] = 0;
+        termios.c_cc[VMIN] = 1;
+        termios
+}
+
+fn toggle(flags: &mut tcflag_t, flag: tcflag_t, enable: Option<bool>)
+{
+        match enable {
+                Some(true) => *flags |= flag,
+                Some(false) => *flags &= !flag,
+                None => {}
+        }
+}
+
+/// Base settings of [`TermiosBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode
+{
+        /// Byte-by-byte input without echo and signals. Same as [`Termios::raw()`].
+        Raw,
+        /// Byte-by-byte input without echo. Ctrl-C and Ctrl-Z still send signals, output is still
+        /// processed. Reads wait for at least one byte.
+        Cbreak,
+        /// Original line-by-line settings. Only options set in [`TermiosBuilder`] are changed.
+        Cooked,
+}
+
+/// Builder of custom [`Termios`] settings.
+///
+/// Starts from the original settings changed by [`Mode`]. Options which are not set keep the
+/// value chosen by the mode.
+///
+/// # Usage
+///
+/// Password prompt, which hides input but keeps line editing and Ctrl-C:
+///
+/// ```no_run
+/// use ruterm::raw::{
+///         Mode,
+///         TermiosBuilder,
+/// };
+/// use std::io;
+///
+/// let termios = TermiosBuilder::new(Mode::Cooked)
+///         .echo(false)
+///         .build(io::stdin())
+///         .unwrap();
+/// termios.apply().unwrap();
+/// let mut password = String::new();
+/// io::stdin().read_line(&mut password).unwrap();
+/// termios.original().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TermiosBuilder
+{
+        mode: Mode,
+        echo: Option<bool>,
+        output_processing: Option<bool>,
+        flow_control: Option<bool>,
+        vmin: Option<u8>,
+        vtime: Option<u8>,
+}
+
+impl TermiosBuilder
+{
+        pub fn new(mode: Mode) -> Self
+        {
+                Self {
+                        mode,
+                        echo: None,
+                        output_processing: None,
+                        flow_control: None,
+                        vmin: None,
+                        vtime: None,
+                }
+        }
+
+        /// Echoing of typed characters (`ECHO`).
+        pub fn echo(mut self, enable: bool) -> Self
+        {
+                self.echo = Some(enable);
+                self
+        }
+
+        /// Output post-processing (`OPOST`), e.g. translating `\n` to `\r\n`.
+        pub fn output_processing(mut self, enable: bool) -> Self
+        {
+                self.output_processing = Some(enable);
+                self
+        }
+
+        /// Software flow control with Ctrl-S and Ctrl-Q (`IXON`).
+        pub fn flow_control(mut self, enable: bool) -> Self
+        {
+                self.flow_control = Some(enable);
+                self
+        }
+
+        /// Minimum number of bytes needed for read (`VMIN`). Used when input isn't canonical.
+        pub fn vmin(mut self, bytes: u8) -> Self
+        {
+                self.vmin = Some(bytes);
+                self
+        }
+
+        /// Read timeout in tenths of a second (`VTIME`). Used when input isn't canonical.
+        pub fn vtime(mut self, tenths: u8) -> Self
+        {
+                self.vtime = Some(tenths);
+                self
+        }
+
+        fn settings(&self, original: Termios_) -> Termios_
+        {
+                let mut termios = match self.mode {
+                        Mode::Raw => raw(original),
+                        Mode::Cbreak => cbreak(original),
+                        Mode::Cooked => original,
+                };
+                toggle(&mut termios.c_lflag, ECHO, self.echo);
+                toggle(&mut termios.c_oflag, OPOST, self.output_processing);
+                toggle(&mut termios.c_iflag, IXON, self.flow_control);
+                if let Some(vmin) = self.vmin {
+                        termios.c_cc[VMIN] = vmin as cc_t;
+                }
+                if let Some(vtime) = self.vtime {
+                        termios.c_cc[VTIME] = vtime as cc_t;
+                }
+                termios
+        }
+
+        /// Reads original settings of `fd`. Settings are changed only by [`Termios::apply()`].
+        pub fn build<D: AsRawFd>(self, fd: D) -> Result<Termios<D>>
+        {
+                let original = termios(&fd)?;
+                Ok(Termios {
+                        original,
+                        settings: self.settings(original),
+                        fd,
+                })
+        }
+}
+
 /// Termios settings.
 ///
-/// Provides abstraction over termios settings.
+/// Provides abstraction over termios settings. See [`TermiosBuilder`] for modes other than raw.
 ///
 /// # Usage
 ///
@@ -54,7 +217,7 @@ fn raw(mut termios: Termios_) -> Termios_
 /// termios.original().unwrap(); // Restore original settings
 /// ```
 ///
-/// # Flags
+/// # Raw flags
 ///
 /// - `ECHO`      disable echoing
 /// - `ICANON`    read byte-by-byte
@@ -78,30 +241,91 @@ pub struct Termios<D: AsRawFd>
 {
         fd: D,
         original: Termios_,
-        raw: Termios_,
+        /// Applied by [`Termios::apply()`].
+        settings: Termios_,
 }
 
 impl<D: AsRawFd> Termios<D>
 {
         pub fn new(fd: D) -> Result<Self>
         {
-                Ok(Self {
-                        original: termios(&fd)?,
-                        raw: raw(termios(&fd)?),
-                        fd, // trick: fd should be the last parameter
-                })
+                TermiosBuilder::new(Mode::Raw).build(fd)
         }
 
         pub fn raw(&self) -> Result<()>
         {
-                tcsetattr(self.fd.as_raw_fd(), TCSAFLUSH, &self.raw)
-                        .map_err(|_| Error("Failed to set raw flags."))
+                self.set(&raw(self.original))
+        }
+
+        /// Applies settings chosen in [`TermiosBuilder`]. Same as [`Termios::raw()`] for
+        /// [`Termios::new()`].
+        pub fn apply(&self) -> Result<()>
+        {
+                self.set(&self.settings)
+        }
+
+        /// Suspends the process, like Ctrl-Z does in cooked mode.
+        ///
+        /// Restores original settings and stops the process with `SIGTSTP`. When the process is
+        /// continued, applies settings of [`Termios::apply()`] again. Enabled mouse reporting,
+        /// bracketed paste and focus reporting are disabled meanwhile, writes to the file
+        /// descriptor of the settings. See also
+        /// [`crate::screen::AlternateScreen::suspend()`].
+        ///
+        /// # Usage
+        ///
+        /// ```no_run
+        /// use ruterm::{
+        ///         event::{
+        ///                 self,
+        ///                 Event,
+        ///                 KeyCode,
+        ///                 KeyEvent,
+        ///                 Modifiers,
+        ///         },
+        ///         raw::Termios,
+        /// };
+        /// use std::io;
+        ///
+        /// let termios = Termios::new(io::stdin()).unwrap();
+        /// termios.apply().unwrap();
+        /// let ctrl_z = KeyEvent::new(
+        ///         KeyCode::Char('z'),
+        ///         Modifiers {
+        ///                 ctrl: true,
+        ///                 ..Modifiers::NONE
+        ///         },
+        /// );
+        /// if event::read() == Some(Event::Key(ctrl_z)) {
+        ///         termios.suspend().unwrap(); // returns after `fg`
+        /// }
+        /// ```
+        ///
+        /// # Note
+        ///
+        /// Contains unsafe bindings!
+        pub fn suspend(&self) -> Result<()>
+        {
+                #[cfg(feature = "event")]
+                pause_reporting_with_output(&mut Fd(self.fd.as_raw_fd()))?;
+                self.original()?;
+                if unsafe { libc::raise(SIGTSTP) } != 0 {
+                        return Err(io::Error::last_os_error().into());
+                }
+                self.apply()?;
+                #[cfg(feature = "event")]
+                resume_reporting_with_output(&mut Fd(self.fd.as_raw_fd()))?;
+                Ok(())
+        }
+
+        fn set(&self, termios: &Termios_) -> Result<()>
+        {
+                tcsetattr(self.fd.as_raw_fd(), TCSAFLUSH, termios).map_err(Error::Termios)
         }
 
         pub fn original(&self) -> Result<()>
         {
-                tcsetattr(self.fd.as_raw_fd(), TCSAFLUSH, &self.original)
-                        .map_err(|_| Error("Failed to restore original flags."))
+                self.set(&self.original)
         }
 }
 
@@ -109,12 +333,15 @@ impl<D: AsRawFd> Drop for Termios<D>
 {
         fn drop(&mut self)
         {
-                self.original().unwrap();
+                // panicking here could abort the process during unwinding
+                let _ = self.original();
         }
 }
 
 /// Enables raw mode in one line.
 ///
+/// Uses stdin, unless another file descriptor is given.
+///
 /// # Usage
 ///
 /// ```ignore
@@ -123,12 +350,20 @@ impl<D: AsRawFd> Drop for Termios<D>
 /// in_raw!({
 ///     // ...
 /// });
+///
+/// let tty = ruterm::tty::Tty::open()?;
+/// in_raw!(&tty, {
+///     // ...
+/// });
 /// ```
 #[macro_export]
 macro_rules! in_raw {
         ($block: block) => {
-                let fd = std::io::stdin();
-                let mut termios = ruterm::raw::Termios::new(fd)?;
+                $crate::in_raw!(std::io::stdin(), $block)
+        };
+        ($fd: expr, $block: block) => {
+                let fd = $fd;
+                let mut termios = $crate::raw::Termios::new(fd)?;
                 termios.raw()?;
                 $block
         };
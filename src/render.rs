@@ -1,4 +1,8 @@
 use crate::{
+        ansi::{
+                Action,
+                Parser,
+        },
         cursor::{
                 self,
                 Direction,
@@ -10,32 +14,35 @@ use std::io::{
         self,
         Write,
 };
-use std::str::Chars;
-
-const ESCAPE_END: [char; 9] = ['m', 'A', 'B', 'C', 'D', 'H', 'J', 'l', 'h'];
-
-fn skip_invisible(line: &mut Chars)
-{
-        for c in line.by_ref() {
-                if ESCAPE_END.contains(&c) {
-                        break;
-                }
-        }
-}
+use unicode_width::UnicodeWidthStr;
 
-fn visible_length(line: &str) -> u16
+/// Number of terminal columns taken by `line`.
+///
+/// Escape sequences (see [`crate::ansi`]) and control characters take no space. Wide characters
+/// (e.g. CJK and emoji) take two columns, while combining and other zero-width characters take
+/// none.
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::{
+///         render::visible_length,
+///         view::color::fore,
+/// };
+///
+/// assert_eq!(6, visible_length(&(fore::RED.to_string() + "日本語")));
+/// ```
+pub fn visible_length(line: &str) -> u16
 {
-        let mut length = 0;
-        let mut line = line.chars();
-        while let Some(c) = line.next() {
-                if c == '\x1b' {
-                        skip_invisible(&mut line);
-                }
-                else {
-                        length += 1;
-                }
-        }
-        length
+        let mut parser = Parser::new();
+        let printed: String = line
+                .chars()
+                .filter_map(|c| match parser.advance(c) {
+                        Some(Action::Print(c)) => Some(c),
+                        _ => None,
+                })
+                .collect();
+        printed.width() as u16
 }
 
 /// Moves cursor to the beginning of the next line. Used in [`render()`].
@@ -122,6 +129,29 @@ mod tests
                 assert_eq!(("Hi".len() + "Hello".len()) as u16, visible_length(&code));
         }
 
+        #[test]
+        fn visible_length_sequences_()
+        {
+                let line = concat!(
+                        "\x1b]8;;https://example.com\x1b\\",
+                        "Link",
+                        "\x1b]8;;\x1b\\",
+                        "\x1b[K"
+                );
+                assert_eq!("Link".len() as u16, visible_length(line));
+                assert_eq!(2, visible_length("a\x07b\r"));
+                assert_eq!(2, visible_length("a\tb"));
+                assert_eq!(3, visible_length("\x1bébc"));
+        }
+
+        #[test]
+        fn visible_length_wide_()
+        {
+                assert_eq!(4, visible_length("日本"));
+                assert_eq!(1, visible_length("e\u{301}"));
+                assert_eq!(2, visible_length("👩\u{200d}💻"));
+        }
+
         #[test]
         fn render_()
         {
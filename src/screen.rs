@@ -0,0 +1,214 @@
+use crate::{
+        cursor,
+        error::Result,
+        raw::Termios,
+        tio::{
+                flush,
+                flush_with_output,
+                write_with_output,
+        },
+};
+use std::{
+        io::{
+                self,
+                Write,
+        },
+        os::fd::AsRawFd,
+        sync::atomic::{
+                AtomicBool,
+                Ordering,
+        },
+};
+
+/// Whether the alternate screen was entered last. Checked by [`crate::panic::install_hook()`].
+pub(crate) static ALTERNATE: AtomicBool = AtomicBool::new(false);
+
+/// Switches to the alternate screen buffer. Writes to `output`. Same as [`enter_alternate_screen()`].
+pub fn enter_alternate_screen_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        ALTERNATE.store(true, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?1049h")
+}
+
+/// Switches to the alternate screen buffer. Writes to stdout.
+///
+/// Content of the main screen and scrollback stays untouched until [`leave_alternate_screen()`].
+pub fn enter_alternate_screen() -> Result<usize>
+{
+        enter_alternate_screen_with_output(&mut io::stdout())
+}
+
+/// Switches back to the main screen buffer. Writes to `output`. Same as [`leave_alternate_screen()`].
+pub fn leave_alternate_screen_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        ALTERNATE.store(false, Ordering::Relaxed);
+        write_with_output(output, "\x1b[?1049l")
+}
+
+/// Switches back to the main screen buffer. Writes to stdout.
+pub fn leave_alternate_screen() -> Result<usize>
+{
+        leave_alternate_screen_with_output(&mut io::stdout())
+}
+
+/// Starts synchronized update. Writes to `output`. Same as [`begin_synchronized_update()`].
+pub fn begin_synchronized_update_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[?2026h")
+}
+
+/// Starts synchronized update (mode 2026). Writes to stdout.
+///
+/// Terminal holds presenting output until [`end_synchronized_update()`], so a frame appears at
+/// once without tearing. Terminals which don't support it ignore the sequence.
+pub fn begin_synchronized_update() -> Result<usize>
+{
+        begin_synchronized_update_with_output(&mut io::stdout())
+}
+
+/// Ends synchronized update. Writes to `output`. Same as [`end_synchronized_update()`].
+pub fn end_synchronized_update_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[?2026l")
+}
+
+/// Ends synchronized update and presents the output. Writes to stdout.
+pub fn end_synchronized_update() -> Result<usize>
+{
+        end_synchronized_update_with_output(&mut io::stdout())
+}
+
+/// Frame guard.
+///
+/// Wraps output written through it in a synchronized update. When dropped, ends the update and
+/// flushes the output.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         cursor,
+///         screen::SynchronizedUpdate,
+///         tio::write_with_output,
+/// };
+/// use std::io;
+///
+/// let mut stdout = io::stdout();
+/// {
+///         let mut frame = SynchronizedUpdate::new(&mut stdout).unwrap();
+///         cursor::start_with_output(&mut frame).unwrap();
+///         write_with_output(&mut frame, "Whole frame at once").unwrap();
+/// } // presented here
+/// ```
+pub struct SynchronizedUpdate<'a, W: Write>
+{
+        output: &'a mut W,
+}
+
+impl<'a, W: Write> SynchronizedUpdate<'a, W>
+{
+        pub fn new(output: &'a mut W) -> Result<Self>
+        {
+                begin_synchronized_update_with_output(output)?;
+                Ok(Self { output })
+        }
+}
+
+impl<W: Write> Write for SynchronizedUpdate<'_, W>
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                self.output.write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                self.output.flush()
+        }
+}
+
+impl<W: Write> Drop for SynchronizedUpdate<'_, W>
+{
+        fn drop(&mut self)
+        {
+                let _ = end_synchronized_update_with_output(self.output);
+                let _ = flush_with_output(self.output);
+        }
+}
+
+/// Full-screen session.
+///
+/// Enables raw mode and enters the alternate screen. When dropped, leaves the alternate screen,
+/// shows the cursor and restores original termios settings.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         cursor,
+///         screen::AlternateScreen,
+///         tio::write,
+/// };
+/// use std::io;
+///
+/// let screen = AlternateScreen::new(io::stdin()).unwrap();
+/// cursor::hide().unwrap();
+/// write("Hello from the alternate screen!").unwrap();
+///
+/// // ...
+///
+/// drop(screen); // back to the shell
+/// ```
+pub struct AlternateScreen<D: AsRawFd>
+{
+        termios: Termios<D>,
+}
+
+impl<D: AsRawFd> AlternateScreen<D>
+{
+        pub fn new(fd: D) -> Result<Self>
+        {
+                let termios = Termios::new(fd)?;
+                termios.raw()?;
+                enter_alternate_screen()?;
+                flush()?;
+                Ok(Self { termios })
+        }
+
+        /// Suspends the process.
+        ///
+        /// Leaves the alternate screen, shows the cursor and calls [`Termios::suspend()`]. When
+        /// the process is continued, enters the alternate screen again and hides the cursor if it
+        /// was hidden. Screen content is lost, so the whole screen should be drawn again, see
+        /// [`crate::event::Event::Redraw`].
+        pub fn suspend(&self) -> Result<()>
+        {
+                let hidden = cursor::HIDDEN.load(Ordering::Relaxed);
+                leave_alternate_screen()?;
+                cursor::show()?;
+                flush()?;
+                self.termios.suspend()?;
+                enter_alternate_screen()?;
+                if hidden {
+                        cursor::hide()?;
+                }
+                flush()
+        }
+
+        /// Termios settings restored on drop.
+        pub fn termios(&self) -> &Termios<D>
+        {
+                &self.termios
+        }
+}
+
+impl<D: AsRawFd> Drop for AlternateScreen<D>
+{
+        fn drop(&mut self)
+        {
+                let _ = leave_alternate_screen();
+                let _ = cursor::show();
+                let _ = flush();
+                // termios is restored when the field is dropped
+        }
+}
@@ -0,0 +1,77 @@
+use crate::{
+        error::Result,
+        tio::write_with_output,
+};
+use std::io::{
+        self,
+        Write,
+};
+
+/// Scrolls content up by `lines`. Writes to `output`. Same as [`up()`].
+pub fn up_with_output(output: &mut dyn Write, lines: u16) -> Result<usize>
+{
+        write_with_output(output, format!("\x1b[{}S", lines))
+}
+
+/// Scrolls content of the scroll region up by `lines`. New blank lines appear at the bottom.
+/// Writes to stdout.
+pub fn up(lines: u16) -> Result<usize>
+{
+        up_with_output(&mut io::stdout(), lines)
+}
+
+/// Scrolls content down by `lines`. Writes to `output`. Same as [`down()`].
+pub fn down_with_output(output: &mut dyn Write, lines: u16) -> Result<usize>
+{
+        write_with_output(output, format!("\x1b[{}T", lines))
+}
+
+/// Scrolls content of the scroll region down by `lines`. New blank lines appear at the top.
+/// Writes to stdout.
+pub fn down(lines: u16) -> Result<usize>
+{
+        down_with_output(&mut io::stdout(), lines)
+}
+
+/// Limits scrolling to lines from `top` to `bottom`. Writes to `output`. Same as [`set_region()`].
+pub fn set_region_with_output(output: &mut dyn Write, top: u16, bottom: u16) -> Result<usize>
+{
+        write_with_output(output, format!("\x1b[{};{}r", top, bottom))
+}
+
+/// Limits scrolling to lines from `top` to `bottom`, inclusive (DECSTBM). Writes to stdout.
+///
+/// Lines start from 1, same as in [`crate::cursor::set()`]. Moves the cursor to the start position.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         cursor,
+///         scroll,
+///         size,
+///         tio::write,
+/// };
+///
+/// let (_, height) = size().unwrap();
+/// scroll::set_region(1, height - 1).unwrap(); // keeps the last line for status
+/// cursor::set(1, height - 1).unwrap();
+/// write("log line\n\r").unwrap();
+/// scroll::reset_region().unwrap();
+/// ```
+pub fn set_region(top: u16, bottom: u16) -> Result<usize>
+{
+        set_region_with_output(&mut io::stdout(), top, bottom)
+}
+
+/// Resets scroll region to the whole screen. Writes to `output`. Same as [`reset_region()`].
+pub fn reset_region_with_output(output: &mut dyn Write) -> Result<usize>
+{
+        write_with_output(output, "\x1b[r")
+}
+
+/// Resets scroll region to the whole screen. Writes to stdout.
+pub fn reset_region() -> Result<usize>
+{
+        reset_region_with_output(&mut io::stdout())
+}
@@ -0,0 +1,289 @@
+use crate::error::Result;
+use libc::{
+        c_int,
+        c_void,
+        sigaction,
+        sigemptyset,
+        sighandler_t,
+        FD_CLOEXEC,
+        F_SETFD,
+        F_SETFL,
+        O_NONBLOCK,
+        SA_RESTART,
+        SIGCONT,
+        SIGWINCH,
+};
+use std::{
+        io,
+        mem,
+        os::fd::{
+                AsRawFd,
+                RawFd,
+        },
+        ptr,
+        sync::{
+                atomic::{
+                        AtomicI32,
+                        Ordering,
+                },
+                Mutex,
+                Once,
+                PoisonError,
+        },
+};
+
+static READER: AtomicI32 = AtomicI32::new(-1);
+static WRITER: AtomicI32 = AtomicI32::new(-1);
+static PIPE: Once = Once::new();
+/// Error of `pipe()` call, reported on every [`Signals::new()`].
+static PIPE_ERROR: AtomicI32 = AtomicI32::new(0);
+/// Handlers are installed by the first [`Signals`] and restored when the last one is dropped.
+static INSTALLED: Mutex<Installed> = Mutex::new(Installed {
+        count: 0,
+        previous: Vec::new(),
+});
+
+const SIGNALS: [c_int; 2] = [SIGWINCH, SIGCONT];
+
+struct Installed
+{
+        count: usize,
+        /// Actions replaced by the handler.
+        previous: Vec<(c_int, sigaction)>,
+}
+
+/// Location of `errno` of the current thread. Null on platforms where it's unknown.
+unsafe fn errno() -> *mut c_int
+{
+        #[cfg(any(
+                target_os = "linux",
+                target_os = "emscripten",
+                target_os = "hurd",
+                target_os = "dragonfly"
+        ))]
+        return libc::__errno_location();
+        #[cfg(any(
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "watchos",
+                target_os = "freebsd"
+        ))]
+        return libc::__error();
+        #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+        return libc::__errno();
+        #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+        return libc::___errno();
+        #[allow(unreachable_code)]
+        ptr::null_mut()
+}
+
+extern "C" fn handler(signal: c_int)
+{
+        // write() may change errno of the interrupted code
+        let location = unsafe { errno() };
+        let saved = if location.is_null() {
+                0
+        }
+        else {
+                unsafe { *location }
+        };
+        let byte = signal as u8;
+        unsafe {
+                libc::write(
+                        WRITER.load(Ordering::Relaxed),
+                        &byte as *const u8 as *const c_void,
+                        1,
+                );
+                if !location.is_null() {
+                        *location = saved;
+                }
+        }
+}
+
+fn pipe() -> Result<()>
+{
+        PIPE.call_once(|| {
+                let mut fds: [c_int; 2] = [-1, -1];
+                if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                        let errno = io::Error::last_os_error().raw_os_error().unwrap_or(0);
+                        PIPE_ERROR.store(errno, Ordering::Release);
+                        return;
+                }
+                for fd in fds {
+                        unsafe {
+                                libc::fcntl(fd, F_SETFL, O_NONBLOCK);
+                                libc::fcntl(fd, F_SETFD, FD_CLOEXEC);
+                        }
+                }
+                WRITER.store(fds[1], Ordering::Release);
+                READER.store(fds[0], Ordering::Release);
+        });
+        match READER.load(Ordering::Acquire) {
+                -1 => {
+                        let errno = PIPE_ERROR.load(Ordering::Acquire);
+                        Err(io::Error::from_raw_os_error(errno).into())
+                }
+                _ => Ok(()),
+        }
+}
+
+/// Sets `action` for `signal`. Returns the previous action.
+fn set(signal: c_int, action: &sigaction) -> Result<sigaction>
+{
+        unsafe {
+                let mut previous: sigaction = mem::zeroed();
+                match libc::sigaction(signal, action, &mut previous) {
+                        0 => Ok(previous),
+                        _ => Err(io::Error::last_os_error().into()),
+                }
+        }
+}
+
+fn install(signal: c_int, handler: sighandler_t) -> Result<sigaction>
+{
+        let mut action: sigaction = unsafe { mem::zeroed() };
+        action.sa_sigaction = handler;
+        action.sa_flags = SA_RESTART;
+        unsafe { sigemptyset(&mut action.sa_mask) };
+        set(signal, &action)
+}
+
+fn restore(previous: &[(c_int, sigaction)])
+{
+        for (signal, action) in previous {
+                let _ = set(*signal, action);
+        }
+}
+
+/// Signal listener.
+///
+/// Installs `SIGWINCH` and `SIGCONT` handlers which write to a pipe (self-pipe trick), so signals
+/// can be waited on together with input. Used in [`crate::event::wait()`].
+///
+/// Listeners share the handlers, previous handlers are restored when the last one is dropped.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         event::{
+///                 self,
+///                 Event,
+///         },
+///         signal::Signals,
+/// };
+/// use std::time::Duration;
+///
+/// let signals = Signals::new().unwrap();
+/// let event = event::wait(&signals, Duration::from_secs(1)).unwrap();
+/// if let Some(Event::Resize(width, height)) = event {
+///         println!("{width}x{height}");
+/// }
+/// ```
+///
+/// # Note
+///
+/// Contains unsafe bindings!
+pub struct Signals
+{
+        reader: RawFd,
+}
+
+impl Signals
+{
+        pub fn new() -> Result<Self>
+        {
+                pipe()?;
+                let mut installed = INSTALLED.lock().unwrap_or_else(PoisonError::into_inner);
+                if installed.count == 0 {
+                        let mut previous = Vec::new();
+                        for signal in SIGNALS {
+                                match install(
+                                        signal,
+                                        handler as extern "C" fn(c_int) as sighandler_t,
+                                ) {
+                                        Ok(action) => previous.push((signal, action)),
+                                        Err(error) => {
+                                                restore(&previous);
+                                                return Err(error);
+                                        }
+                                }
+                        }
+                        installed.previous = previous;
+                }
+                installed.count += 1;
+                Ok(Self {
+                        reader: READER.load(Ordering::Acquire),
+                })
+        }
+
+        /// Takes all pending signals from the pipe.
+        pub(crate) fn pending(&self) -> Vec<c_int>
+        {
+                let mut buffer = [0u8; 64];
+                let mut signals = Vec::new();
+                loop {
+                        let n = unsafe {
+                                libc::read(
+                                        self.reader,
+                                        buffer.as_mut_ptr() as *mut c_void,
+                                        buffer.len(),
+                                )
+                        };
+                        if n <= 0 {
+                                return signals;
+                        }
+                        signals.extend(buffer[..n as usize].iter().map(|b| *b as c_int));
+                }
+        }
+}
+
+impl AsRawFd for Signals
+{
+        fn as_raw_fd(&self) -> RawFd
+        {
+                self.reader
+        }
+}
+
+impl Drop for Signals
+{
+        fn drop(&mut self)
+        {
+                let mut installed = INSTALLED.lock().unwrap_or_else(PoisonError::into_inner);
+                installed.count -= 1;
+                if installed.count == 0 {
+                        restore(&installed.previous);
+                        installed.previous.clear();
+                }
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        fn pending_()
+        {
+                let signals = Signals::new().unwrap();
+                unsafe {
+                        libc::raise(SIGWINCH);
+                        libc::raise(SIGCONT);
+                }
+                assert_eq!(vec![SIGWINCH, SIGCONT], signals.pending());
+                assert!(signals.pending().is_empty());
+                // handlers stay installed while another listener is alive
+                drop(Signals::new().unwrap());
+                unsafe {
+                        libc::raise(SIGWINCH);
+                }
+                assert_eq!(vec![SIGWINCH], signals.pending());
+                drop(signals);
+                let mut action: sigaction = unsafe { mem::zeroed() };
+                unsafe { libc::sigaction(SIGWINCH, ptr::null(), &mut action) };
+                assert_eq!(libc::SIG_DFL, action.sa_sigaction);
+        }
+}
@@ -1,6 +1,13 @@
-use crate::error::{
-        Error,
-        Result,
+use crate::{
+        error::{
+                Error,
+                Result,
+        },
+        tio::{
+                flush_with_output,
+                read_reply_with_input,
+                write_with_output,
+        },
 };
 use libc::{
         c_ushort,
@@ -8,10 +15,18 @@ use libc::{
         TIOCGWINSZ,
 };
 use std::{
-        io,
+        env,
+        io::{
+                self,
+                Write,
+        },
         os::fd::AsRawFd,
+        time::Duration,
 };
 
+/// How long [`query_size()`] waits for the terminal to reply.
+pub const SIZE_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[repr(C)]
 struct Size
 {
@@ -21,29 +36,173 @@ struct Size
         ws_ypixel: c_ushort,
 }
 
-/// Gets terminal size.
+/// Terminal size in cells and pixels. Returned by [`size_of()`].
+///
+/// Pixel dimensions are 0 when the terminal doesn't report them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WindowSize
+{
+        pub cols: u16,
+        pub rows: u16,
+        pub width_px: u16,
+        pub height_px: u16,
+}
+
+impl WindowSize
+{
+        /// Size of one cell in pixels (width, height), if pixel dimensions are known.
+        pub fn cell_size(&self) -> Option<(u16, u16)>
+        {
+                if self.cols == 0 || self.rows == 0 || self.width_px == 0 || self.height_px == 0 {
+                        return None;
+                }
+                Some((self.width_px / self.cols, self.height_px / self.rows))
+        }
+}
+
+fn ioctl_size(fd: impl AsRawFd) -> Result<WindowSize>
+{
+        let size = Size {
+                ws_row: 0,
+                ws_col: 0,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+        };
+        match unsafe { ioctl(fd.as_raw_fd(), TIOCGWINSZ, &size) } {
+                0 if size.ws_col == 0 || size.ws_row == 0 => {
+                        Err(Error::Unsupported("terminal size report"))
+                }
+                0 => Ok(WindowSize {
+                        cols: size.ws_col,
+                        rows: size.ws_row,
+                        width_px: size.ws_xpixel,
+                        height_px: size.ws_ypixel,
+                }),
+                _ => Err(io::Error::last_os_error().into()),
+        }
+}
+
+fn env_size() -> Option<WindowSize>
+{
+        let variable = |name| env::var(name).ok()?.trim().parse::<u16>().ok();
+        Some(WindowSize {
+                cols: variable("COLUMNS")?,
+                rows: variable("LINES")?,
+                ..WindowSize::default()
+        })
+}
+
+/// Gets size of the terminal referred to by `fd`.
+///
+/// Falls back to `COLUMNS` and `LINES` environment variables when the terminal can't be queried,
+/// e.g. when `fd` is redirected. See also [`query_size()`].
 ///
 /// # Usage
 ///
 /// ```no_run
-/// use ruterm::size;
+/// use ruterm::size_of;
+/// use std::io;
 ///
-/// let (width, height) = size().unwrap();
+/// let size = size_of(io::stdout()).unwrap();
+/// if let Some((width, height)) = size.cell_size() {
+///         println!("cell is {width}x{height} pixels");
+/// }
 /// ```
 ///
 /// # Note
 ///
 /// Contains unsafe bindings!
+pub fn size_of(fd: impl AsRawFd) -> Result<WindowSize>
+{
+        ioctl_size(fd).or_else(|error| env_size().ok_or(error))
+}
+
+/// Gets size of the terminal referred to by `output`. Same as [`size()`].
+pub fn size_with_output(output: &impl AsRawFd) -> Result<(u16, u16)>
+{
+        let size = size_of(output.as_raw_fd())?;
+        Ok((size.cols, size.rows))
+}
+
+/// Gets terminal size. Queries stdout.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::size;
+///
+/// let (width, height) = size().unwrap();
+/// ```
 pub fn size() -> Result<(u16, u16)>
 {
-        let size = Size {
-                ws_row: 0,
-                ws_col: 0,
-                ws_xpixel: 0,
-                ws_ypixel: 0,
-        };
-        match unsafe { ioctl(io::stdout().as_raw_fd(), TIOCGWINSZ, &size) } {
-                0 => Ok((size.ws_col, size.ws_row)),
-                _ => Err(Error("failed to get terminal size")),
+        size_with_output(&io::stdout())
+}
+
+/// Parses `ESC [ 8 ; rows ; cols t`.
+fn parse_size(reply: &[u8]) -> Option<WindowSize>
+{
+        let reply = std::str::from_utf8(reply).ok()?;
+        let (rows, cols) = reply
+                .strip_prefix("\x1b[8;")?
+                .strip_suffix('t')?
+                .split_once(';')?;
+        Some(WindowSize {
+                cols: cols.parse().ok()?,
+                rows: rows.parse().ok()?,
+                ..WindowSize::default()
+        })
+}
+
+/// Asks the terminal for its size. Writes query to `output` and reads reply from `input`. Same as
+/// [`query_size()`].
+pub fn query_size_with_io(output: &mut dyn Write, input: &impl AsRawFd) -> Result<WindowSize>
+{
+        write_with_output(output, "\x1b[18t")?;
+        flush_with_output(output)?;
+        let reply = read_reply_with_input(input, b't', SIZE_TIMEOUT)?
+                .ok_or(Error::Timeout("terminal size query"))?;
+        parse_size(&reply).ok_or(Error::Parse("terminal size report"))
+}
+
+/// Asks the terminal for its size with `CSI 18 t`. Writes to stdout and reads from stdin.
+///
+/// Works when [`size_of()`] can't use ioctl, e.g. over a serial line. Pixel dimensions are not
+/// reported. Should be used in raw mode. Fails if the terminal doesn't reply in [`SIZE_TIMEOUT`].
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         query_size,
+///         size_of,
+/// };
+/// use std::io;
+///
+/// let size = size_of(io::stdout()).or_else(|_| query_size()).unwrap();
+/// ```
+///
+/// # Note
+///
+/// Input which arrives before the reply is discarded.
+pub fn query_size() -> Result<WindowSize>
+{
+        query_size_with_io(&mut io::stdout(), &io::stdin())
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+        use std::os::unix::net::UnixStream;
+
+        #[test]
+        fn query_size_()
+        {
+                let (mut terminal, input) = UnixStream::pair().unwrap();
+                terminal.write_all(b"x\x1b[8;24;80t").unwrap();
+                let mut output = Vec::new();
+                let size = query_size_with_io(&mut output, &input).unwrap();
+                assert_eq!(b"\x1b[18t".to_vec(), output);
+                assert_eq!((80, 24), (size.cols, size.rows));
         }
 }
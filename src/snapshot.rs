@@ -0,0 +1,253 @@
+use crate::{
+        emulator::Emulator,
+        view::{
+                Color,
+                Modifier,
+                Style,
+        },
+};
+use std::{
+        env,
+        fs,
+        path::{
+                Path,
+                PathBuf,
+        },
+};
+
+/// Environment variable which makes [`assert_snapshot()`] overwrite stored snapshots.
+pub const UPDATE: &str = "RUTERM_UPDATE_SNAPSHOTS";
+
+const MODIFIERS: [(Modifier, &str); 7] = [
+        (Modifier::BOLD, "bold"),
+        (Modifier::DIM, "dim"),
+        (Modifier::ITALIC, "italic"),
+        (Modifier::UNDERLINE, "underline"),
+        (Modifier::BLINK, "blink"),
+        (Modifier::REVERSE, "reverse"),
+        (Modifier::STRIKE, "strike"),
+];
+
+/// Plain-text snapshot of the screen.
+///
+/// Contains every line of the screen with trailing spaces removed.
+pub fn text(emulator: &Emulator) -> String
+{
+        let lines: Vec<String> = (0..emulator.buffer().height())
+                .map(|y| emulator.line(y))
+                .collect();
+        lines.join("\n") + "\n"
+}
+
+fn describe(style: Style) -> String
+{
+        let mut parts = Vec::new();
+        if style.fg != Color::Reset {
+                parts.push(format!("fg={:?}", style.fg));
+        }
+        if style.bg != Color::Reset {
+                parts.push(format!("bg={:?}", style.bg));
+        }
+        for (modifier, name) in MODIFIERS {
+                if style.modifier.contains(modifier) {
+                        parts.push(name.to_string());
+                }
+        }
+        parts.join(" ")
+}
+
+/// Snapshot of the screen with styles.
+///
+/// Same as [`text()`], followed by a `--` line and one line per run of styled cells in form
+/// `y:start..end style`. Coordinates start from 0.
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::{
+///         emulator::Emulator,
+///         snapshot,
+///         tio::write_with_output,
+/// };
+///
+/// let mut emulator = Emulator::new(8, 1);
+/// write_with_output(&mut emulator, "ok \x1b[1;32mdone\x1b[m").unwrap();
+/// assert_eq!("ok done\n--\n0:3..7 fg=Green bold\n", snapshot::styled(&emulator));
+/// ```
+pub fn styled(emulator: &Emulator) -> String
+{
+        let buffer = emulator.buffer();
+        let mut snapshot = text(emulator) + "--\n";
+        for y in 0..buffer.height() {
+                let mut x = 0;
+                while x < buffer.width() {
+                        let style = buffer.get(x, y).unwrap().style;
+                        let start = x;
+                        while x < buffer.width() && buffer.get(x, y).unwrap().style == style {
+                                x += 1;
+                        }
+                        if style != Style::new() {
+                                snapshot +=
+                                        &format!("{}:{}..{} {}\n", y, start, x, describe(style));
+                        }
+                }
+        }
+        snapshot
+}
+
+/// Line diff of `expected` and `actual`.
+///
+/// Unchanged lines start with two spaces, removed ones with `- ` and added ones with `+ `.
+pub fn diff(expected: &str, actual: &str) -> String
+{
+        let expected: Vec<&str> = expected.lines().collect();
+        let actual: Vec<&str> = actual.lines().collect();
+        // longest common subsequence of lines, counted from the end
+        let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+        for i in (0..expected.len()).rev() {
+                for j in (0..actual.len()).rev() {
+                        common[i][j] = if expected[i] == actual[j] {
+                                common[i + 1][j + 1] + 1
+                        }
+                        else {
+                                common[i + 1][j].max(common[i][j + 1])
+                        };
+                }
+        }
+        let mut diff = String::new();
+        let (mut i, mut j) = (0, 0);
+        while i < expected.len() || j < actual.len() {
+                if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+                        diff += &format!("  {}\n", expected[i]);
+                        (i, j) = (i + 1, j + 1);
+                }
+                else if j == actual.len()
+                        || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+                {
+                        diff += &format!("- {}\n", expected[i]);
+                        i += 1;
+                }
+                else {
+                        diff += &format!("+ {}\n", actual[j]);
+                        j += 1;
+                }
+        }
+        diff
+}
+
+fn resolve(path: &Path) -> PathBuf
+{
+        match env::var_os("CARGO_MANIFEST_DIR") {
+                Some(root) if path.is_relative() => Path::new(&root).join(path),
+                _ => path.to_path_buf(),
+        }
+}
+
+/// Compares `actual` with snapshot stored in `path`.
+///
+/// Relative paths start from the package root. Snapshots are created and overwritten only when
+/// [`UPDATE`] environment variable is set.
+///
+/// # Panics
+///
+/// If `actual` doesn't match the stored snapshot, with a diff of them, or if the snapshot is
+/// missing, with the new content.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         emulator::Emulator,
+///         render::{
+///                 render_with_output,
+///                 END,
+///         },
+///         snapshot,
+/// };
+///
+/// let mut emulator = Emulator::new(5, 3);
+/// render_with_output(&mut emulator, vec!["* *", END, " * ", END, "* *"]).unwrap();
+/// snapshot::assert_snapshot("snapshots/star.snap", &snapshot::styled(&emulator));
+/// ```
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str)
+{
+        check(
+                &resolve(path.as_ref()),
+                actual,
+                env::var_os(UPDATE).is_some(),
+        );
+}
+
+fn check(path: &Path, actual: &str, update: bool)
+{
+        if update {
+                if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent).expect("failed to create directory");
+                }
+                fs::write(path, actual).expect("failed to write snapshot");
+                return;
+        }
+        match fs::read_to_string(path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => panic!(
+                        "snapshot {} doesn't match (- expected, + actual):\n{}set {} to update it",
+                        path.display(),
+                        diff(&expected, actual),
+                        UPDATE
+                ),
+                Err(_) => panic!(
+                        "snapshot {} is missing, new content:\n{}set {} to create it",
+                        path.display(),
+                        actual,
+                        UPDATE
+                ),
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        fn diff_()
+        {
+                assert_eq!(
+                        "  a\n- b\n+ B\n  c\n+ d\n",
+                        diff("a\nb\nc\n", "a\nB\nc\nd\n")
+                );
+        }
+
+        #[test]
+        #[should_panic(expected = "is missing")]
+        fn missing_()
+        {
+                check(Path::new("src/snapshots/missing.snap"), "text\n", false);
+        }
+
+        #[test]
+        #[cfg(feature = "render")]
+        fn render_()
+        {
+                use crate::{
+                        cursor,
+                        render::{
+                                render_with_output,
+                                END,
+                        },
+                        view::{
+                                color::fore,
+                                RESET,
+                        },
+                };
+
+                let mut emulator = Emulator::new(8, 4);
+                cursor::set_with_output(&mut emulator, 2, 1).unwrap();
+                render_with_output(
+                        &mut emulator,
+                        vec![fore::GREEN, "* *", END, " * ", END, "* *", RESET],
+                )
+                .unwrap();
+                assert_snapshot("src/snapshots/render.snap", &styled(&emulator));
+        }
+}
@@ -0,0 +1,241 @@
+use crate::{
+        buffer::Buffer,
+        cursor::Direction,
+        error::Result,
+        view::Style,
+        writer::TermWriter,
+};
+use std::io::{
+        self,
+        Stdout,
+        Write,
+};
+
+/// Double-buffered screen.
+///
+/// Keeps the previous frame and writes only cells which changed since then. Output of each frame
+/// is collected in [`TermWriter`] and written at once, wrapped in a synchronized update (see
+/// [`crate::screen::begin_synchronized_update()`]).
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         terminal::Terminal,
+///         view::{
+///                 Color,
+///                 Style,
+///         },
+/// };
+///
+/// let mut terminal = Terminal::new().unwrap();
+/// for i in 0..10 {
+///         terminal.draw(|frame| {
+///                 frame.write(i, 0, "*", Style::new().fg(Color::Red));
+///         })
+///         .unwrap();
+/// }
+/// ```
+pub struct Terminal<W: Write>
+{
+        output: TermWriter<W>,
+        previous: Buffer,
+        current: Buffer,
+        clear: bool,
+}
+
+#[cfg(feature = "size")]
+impl Terminal<Stdout>
+{
+        /// Creates terminal of [`crate::size()`]. Writes to stdout.
+        pub fn new() -> Result<Self>
+        {
+                let (width, height) = crate::size()?;
+                Ok(Self::with_output(io::stdout(), width, height))
+        }
+}
+
+impl<W: Write> Terminal<W>
+{
+        pub fn with_output(output: W, width: u16, height: u16) -> Self
+        {
+                Self {
+                        output: TermWriter::new(output),
+                        previous: Buffer::new(width, height),
+                        current: Buffer::new(width, height),
+                        clear: true,
+                }
+        }
+
+        pub fn output(&self) -> &W
+        {
+                self.output.get_ref()
+        }
+
+        /// Changes size of the buffers. Next frame is drawn from scratch.
+        pub fn resize(&mut self, width: u16, height: u16)
+        {
+                self.previous = Buffer::new(width, height);
+                self.current = Buffer::new(width, height);
+                self.clear = true;
+        }
+
+        /// Draws next frame from scratch, e.g. after [`crate::event::Event::Redraw`].
+        pub fn invalidate(&mut self)
+        {
+                self.clear = true;
+        }
+
+        /// Draws next frame.
+        ///
+        /// `frame` starts blank. After drawing, differences from the previous frame are written
+        /// and the output is flushed.
+        pub fn draw<F>(&mut self, frame: F) -> Result<()>
+        where
+                F: FnOnce(&mut Buffer),
+        {
+                self.current.clear();
+                frame(&mut self.current);
+                self.output.begin_synchronized_update();
+                if self.clear {
+                        self.output.start();
+                        self.previous.clear();
+                        self.clear = false;
+                }
+                self.write_difference();
+                self.output.end_synchronized_update();
+                std::mem::swap(&mut self.previous, &mut self.current);
+                self.output.flush()
+        }
+
+        fn write_difference(&mut self)
+        {
+                let output = &mut self.output;
+                // position after the last written cell and style in use
+                let mut position = None;
+                let mut style = Style::new();
+                for y in 0..self.current.height() {
+                        for x in 0..self.current.width() {
+                                let cell = self.current.get(x, y).unwrap();
+                                // continuation cells are covered by the wide character before
+                                if cell.is_continuation() || Some(cell) == self.previous.get(x, y) {
+                                        continue;
+                                }
+                                match position {
+                                        Some((px, py)) if (px, py) == (x, y) => {}
+                                        Some((px, py)) if py == y && px < x => {
+                                                output.move_(Direction::Right, x - px);
+                                        }
+                                        _ => {
+                                                output.set(x + 1, y + 1);
+                                        }
+                                }
+                                if cell.style != style {
+                                        output.style_diff(style, cell.style);
+                                        style = cell.style;
+                                }
+                                output.display(cell.symbol);
+                                position = Some((x + cell.width(), y));
+                        }
+                }
+                if style != Style::new() {
+                        output.reset();
+                }
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+        use crate::view::Color;
+
+        fn text(output: &[u8]) -> String
+        {
+                String::from_utf8(output.to_vec()).unwrap()
+        }
+
+        #[test]
+        fn draw_()
+        {
+                let mut terminal = Terminal::with_output(Vec::new(), 4, 2);
+                terminal.draw(|frame| frame.write(0, 0, "ab", Style::new()))
+                        .unwrap();
+                assert_eq!(
+                        concat!(
+                                "\x1b[?2026h",
+                                "\x1b[H\x1b[2J",
+                                "\x1b[1;1H",
+                                "ab",
+                                "\x1b[?2026l"
+                        ),
+                        text(terminal.output())
+                );
+        }
+
+        #[test]
+        fn difference_()
+        {
+                let mut terminal = Terminal::with_output(Vec::new(), 4, 2);
+                terminal.draw(|frame| frame.write(0, 0, "ab", Style::new()))
+                        .unwrap();
+                let length = terminal.output().len();
+                terminal.draw(|frame| {
+                        frame.write(0, 0, "a", Style::new());
+                        frame.write(3, 1, "c", Style::new().fg(Color::Red));
+                })
+                .unwrap();
+                assert_eq!(
+                        concat!(
+                                "\x1b[?2026h",
+                                "\x1b[1;2H",
+                                " ",
+                                "\x1b[2;4H",
+                                "\x1b[31m",
+                                "c",
+                                "\x1b[m",
+                                "\x1b[?2026l"
+                        ),
+                        text(&terminal.output()[length..])
+                );
+        }
+
+        #[test]
+        fn wide_()
+        {
+                use crate::emulator::Emulator;
+
+                let mut terminal = Terminal::with_output(Emulator::new(6, 1), 6, 1);
+                terminal.draw(|frame| frame.write(0, 0, "日本x", Style::new()))
+                        .unwrap();
+                assert_eq!("日本x", terminal.output().line(0));
+                assert_eq!('x', terminal.output().cell(4, 0).unwrap().symbol);
+                terminal.draw(|frame| frame.write(0, 0, "a本x", Style::new()))
+                        .unwrap();
+                assert_eq!("a本x", terminal.output().line(0));
+                assert_eq!('x', terminal.output().cell(3, 0).unwrap().symbol);
+        }
+
+        #[test]
+        fn wide_overlap_()
+        {
+                use crate::emulator::Emulator;
+
+                let mut terminal = Terminal::with_output(Emulator::new(4, 1), 4, 1);
+                terminal.draw(|frame| {
+                        frame.write(0, 0, "日", Style::new());
+                        frame.write(1, 0, "x", Style::new());
+                })
+                .unwrap();
+                assert_eq!(" x", terminal.output().line(0));
+                terminal.draw(|frame| frame.write(0, 0, "日", Style::new()))
+                        .unwrap();
+                assert_eq!("日", terminal.output().line(0));
+                terminal.draw(|frame| {
+                        frame.write(1, 0, "本", Style::new());
+                        frame.write(0, 0, "a", Style::new());
+                })
+                .unwrap();
+                assert_eq!("a本", terminal.output().line(0));
+        }
+}
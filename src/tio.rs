@@ -1,18 +1,32 @@
-use crate::error::{
-        Error,
-        Result,
+use crate::error::Result;
+use libc::{
+        c_int,
+        c_void,
+        pollfd,
+        POLLIN,
 };
-use std::io::{
-        self,
-        Read,
-        Write,
+use std::{
+        io::{
+                self,
+                ErrorKind,
+                Read,
+                Write,
+        },
+        os::fd::{
+                AsRawFd,
+                RawFd,
+        },
+        result::Result as Result_,
+        time::{
+                Duration,
+                Instant,
+        },
 };
 
 /// Writes `content` to `output`. Same as [`write()`].
 pub fn write_with_output<T: ToString>(output: &mut dyn Write, content: T) -> Result<usize>
 {
-        output.write(content.to_string().as_bytes())
-                .map_err(|_| Error("failed to write"))
+        Ok(output.write(content.to_string().as_bytes())?)
 }
 
 /// Writes `content` to stdout.
@@ -57,10 +71,226 @@ pub fn read() -> Option<u8>
         read_with_input(&mut io::stdin())
 }
 
+fn utf8_length(first: u8) -> usize
+{
+        match first {
+                0x00..=0x7f => 1,
+                0xc2..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf4 => 4,
+                _ => 0,
+        }
+}
+
+/// Completes UTF-8 sequence started by `first`. Used in [`read_char_with_input()`].
+///
+/// Fails with the byte that broke a truncated sequence, so it can be decoded on its own, or with
+/// `None` if the sequence is invalid or the input ends.
+pub(crate) fn utf8_with_input(first: u8, input: &mut impl Read) -> Result_<char, Option<u8>>
+{
+        let length = utf8_length(first);
+        if length == 0 {
+                return Err(None);
+        }
+        let mut buffer: [u8; 4] = [first, 0, 0, 0];
+        for byte in buffer.iter_mut().take(length).skip(1) {
+                match read_with_input(input) {
+                        Some(next) if next & 0xc0 == 0x80 => *byte = next,
+                        next => return Err(next),
+                }
+        }
+        std::str::from_utf8(&buffer[..length])
+                .ok()
+                .and_then(|s| s.chars().next())
+                .ok_or(None)
+}
+
+/// Reads one UTF-8 character from `input`. Same as [`read_char()`].
+pub fn read_char_with_input(input: &mut impl Read) -> Option<char>
+{
+        let first = read_with_input(input)?;
+        Some(utf8_with_input(first, input).unwrap_or(char::REPLACEMENT_CHARACTER))
+}
+
+/// Reads one UTF-8 character from stdin.
+///
+/// Returns `None` when there is no input.
+///
+/// Invalid or truncated sequences are returned as [`char::REPLACEMENT_CHARACTER`]. The byte that
+/// broke the sequence is consumed too.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::tio::read_char;
+///
+/// if let Some(c) = read_char() {
+///         println!("{c}");
+/// }
+/// ```
+pub fn read_char() -> Option<char>
+{
+        read_char_with_input(&mut io::stdin())
+}
+
+/// Unbuffered reader and writer of a file descriptor.
+pub(crate) struct Fd(pub RawFd);
+
+impl Write for Fd
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                match unsafe { libc::write(self.0, bytes.as_ptr() as *const c_void, bytes.len()) } {
+                        -1 => Err(io::Error::last_os_error()),
+                        n => Ok(n as usize),
+                }
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                Ok(())
+        }
+}
+
+impl Read for Fd
+{
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+        {
+                match unsafe {
+                        libc::read(self.0, buffer.as_mut_ptr() as *mut c_void, buffer.len())
+                } {
+                        -1 => Err(io::Error::last_os_error()),
+                        n => Ok(n as usize),
+                }
+        }
+}
+
+fn milliseconds(timeout: Duration) -> c_int
+{
+        timeout.as_nanos()
+                .div_ceil(1_000_000)
+                .min(c_int::MAX as u128) as c_int
+}
+
+/// Waits until any of `fds` is ready. Interrupts by signals are retried until the deadline.
+pub(crate) fn poll_fds(fds: &mut [pollfd], timeout: Duration) -> Result<bool>
+{
+        let deadline = Instant::now() + timeout;
+        loop {
+                let timeout = milliseconds(deadline.saturating_duration_since(Instant::now()));
+                match unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) } {
+                        -1 if io::Error::last_os_error().kind() == ErrorKind::Interrupted => {}
+                        -1 => return Err(io::Error::last_os_error().into()),
+                        0 => return Ok(false),
+                        _ => return Ok(true),
+                }
+        }
+}
+
+/// Waits until `input` is ready for reading. Same as [`poll()`].
+pub fn poll_with_input(input: &impl AsRawFd, timeout: Duration) -> Result<bool>
+{
+        let mut fds = [pollfd {
+                fd: input.as_raw_fd(),
+                events: POLLIN,
+                revents: 0,
+        }];
+        poll_fds(&mut fds, timeout)
+}
+
+/// Waits until stdin is ready for reading or `timeout` expires.
+///
+/// Returns `true` if there is input. Interrupts by signals are retried until the deadline.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::tio::poll;
+/// use std::time::Duration;
+///
+/// if poll(Duration::from_millis(16)).unwrap() {
+///         // ...
+/// }
+/// ```
+///
+/// # Note
+///
+/// Polls the file descriptor, so bytes already taken into the buffer of [`io::stdin()`] are not
+/// seen.
+///
+/// Contains unsafe bindings!
+pub fn poll(timeout: Duration) -> Result<bool>
+{
+        poll_with_input(&io::stdin(), timeout)
+}
+
+/// Reads one byte from `input`, waiting at most `timeout`. Same as [`read_timeout()`].
+pub fn read_timeout_with_input(input: &impl AsRawFd, timeout: Duration) -> Result<Option<u8>>
+{
+        if !poll_with_input(input, timeout)? {
+                return Ok(None);
+        }
+        Ok(read_with_input(&mut Fd(input.as_raw_fd())))
+}
+
+/// Reads one byte from stdin, waiting at most `timeout`.
+///
+/// Returns `None` if `timeout` expires.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::tio::read_timeout;
+/// use std::time::Duration;
+///
+/// if let Some(byte) = read_timeout(Duration::from_secs(1)).unwrap() {
+///         println!("{byte}");
+/// }
+/// ```
+///
+/// # Note
+///
+/// Reads the file descriptor directly, bypassing the buffer of [`io::stdin()`].
+///
+/// Contains unsafe bindings!
+pub fn read_timeout(timeout: Duration) -> Result<Option<u8>>
+{
+        read_timeout_with_input(&io::stdin(), timeout)
+}
+
+/// Reads terminal reply `ESC ... end` from `input`, waiting at most `timeout` in total.
+///
+/// Returns `None` if `timeout` expires. Input which arrives before the reply is dropped.
+pub(crate) fn read_reply_with_input(
+        input: &impl AsRawFd,
+        end: u8,
+        timeout: Duration,
+) -> Result<Option<Vec<u8>>>
+{
+        let deadline = Instant::now() + timeout;
+        let mut reply = Vec::new();
+        loop {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                let Some(byte) = read_timeout_with_input(input, timeout)?
+                else {
+                        return Ok(None);
+                };
+                match byte {
+                        b'\x1b' => reply = vec![byte],
+                        _ if byte == end && !reply.is_empty() => {
+                                reply.push(byte);
+                                return Ok(Some(reply));
+                        }
+                        _ if !reply.is_empty() => reply.push(byte),
+                        _ => {}
+                }
+        }
+}
+
 /// Flushes `output`. Same as [`flush()`].
 pub fn flush_with_output(output: &mut dyn Write) -> Result<()>
 {
-        output.flush().map_err(|_| Error("failed to flush"))
+        Ok(output.flush()?)
 }
 
 /// Flushes stdout.
@@ -68,3 +298,24 @@ pub fn flush() -> Result<()>
 {
         flush_with_output(&mut io::stdout())
 }
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+        use std::os::unix::net::UnixStream;
+
+        #[test]
+        fn read_timeout_()
+        {
+                let (mut writer, reader) = UnixStream::pair().unwrap();
+                let timeout = Duration::from_millis(10);
+                assert_eq!(None, read_timeout_with_input(&reader, timeout).unwrap());
+                writer.write_all(b"x").unwrap();
+                assert!(poll_with_input(&reader, timeout).unwrap());
+                assert_eq!(
+                        Some(b'x'),
+                        read_timeout_with_input(&reader, timeout).unwrap()
+                );
+        }
+}
@@ -0,0 +1,123 @@
+use crate::error::Result;
+use std::{
+        fs::{
+                File,
+                OpenOptions,
+        },
+        io::{
+                self,
+                Read,
+                Write,
+        },
+        os::fd::{
+                AsRawFd,
+                RawFd,
+        },
+};
+
+/// Controlling terminal of the process.
+///
+/// Opens `/dev/tty` for reading and writing, so the terminal can be used even when stdin or
+/// stdout are redirected, e.g. in `cmd | tool | cmd` pipelines.
+///
+/// Can be passed to [`crate::raw::Termios::new()`], [`crate::size_with_output()`] and
+/// `_with_output` functions. References implement the same traits, so one [`Tty`] can be shared
+/// between them.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         cursor,
+///         raw::Termios,
+///         tio::write_with_output,
+///         tty::Tty,
+/// };
+///
+/// let tty = Tty::open().unwrap();
+/// let termios = Termios::new(&tty).unwrap();
+/// termios.raw().unwrap();
+/// let mut output = &tty;
+/// cursor::start_with_output(&mut output).unwrap();
+/// write_with_output(&mut output, "Works inside of a pipeline").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Tty
+{
+        file: File,
+}
+
+impl Tty
+{
+        /// Opens `/dev/tty`.
+        pub fn open() -> Result<Self>
+        {
+                let file = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+                Ok(Self { file })
+        }
+
+        /// Creates another handle to the same terminal.
+        pub fn try_clone(&self) -> Result<Self>
+        {
+                let file = self.file.try_clone()?;
+                Ok(Self { file })
+        }
+}
+
+impl AsRawFd for Tty
+{
+        fn as_raw_fd(&self) -> RawFd
+        {
+                self.file.as_raw_fd()
+        }
+}
+
+impl AsRawFd for &Tty
+{
+        fn as_raw_fd(&self) -> RawFd
+        {
+                self.file.as_raw_fd()
+        }
+}
+
+impl Read for Tty
+{
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+        {
+                self.file.read(buffer)
+        }
+}
+
+impl Read for &Tty
+{
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+        {
+                (&self.file).read(buffer)
+        }
+}
+
+impl Write for Tty
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                self.file.write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                self.file.flush()
+        }
+}
+
+impl Write for &Tty
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                (&self.file).write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                (&self.file).flush()
+        }
+}
@@ -1,3 +1,14 @@
+use std::{
+        fmt::{
+                Display,
+                Formatter,
+        },
+        ops::{
+                BitOr,
+                BitOrAssign,
+        },
+};
+
 pub const RESET: &str = "\x1b[m";
 
 /// Color codes.
@@ -117,3 +128,311 @@ pub mod style
         pub const BLINK: &str = "\x1b[5m";
         pub const STRIKE: &str = "\x1b[9m";
 }
+
+/// Color of [`Style`].
+///
+/// # References
+///
+/// - [Wikipedia](https://en.wikipedia.org/wiki/ANSI_escape_code#Colors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Color
+{
+        /// Default color of the terminal.
+        #[default]
+        Reset,
+        Black,
+        Red,
+        Green,
+        Yellow,
+        Blue,
+        Magenta,
+        Cyan,
+        White,
+        BrightBlack,
+        BrightRed,
+        BrightGreen,
+        BrightYellow,
+        BrightBlue,
+        BrightMagenta,
+        BrightCyan,
+        BrightWhite,
+        /// Color from the 256-color palette.
+        Indexed(u8),
+        Rgb(u8, u8, u8),
+}
+
+impl Color
+{
+        /// Writes SGR parameters. `base` is 30 for foreground and 40 for background.
+        fn write_parameters(self, f: &mut Formatter<'_>, base: u8) -> std::fmt::Result
+        {
+                let named = |offset: u8| offset + base;
+                let bright = |offset: u8| offset + base + 60;
+                match self {
+                        Self::Reset => write!(f, "{}", named(9)),
+                        Self::Black => write!(f, "{}", named(0)),
+                        Self::Red => write!(f, "{}", named(1)),
+                        Self::Green => write!(f, "{}", named(2)),
+                        Self::Yellow => write!(f, "{}", named(3)),
+                        Self::Blue => write!(f, "{}", named(4)),
+                        Self::Magenta => write!(f, "{}", named(5)),
+                        Self::Cyan => write!(f, "{}", named(6)),
+                        Self::White => write!(f, "{}", named(7)),
+                        Self::BrightBlack => write!(f, "{}", bright(0)),
+                        Self::BrightRed => write!(f, "{}", bright(1)),
+                        Self::BrightGreen => write!(f, "{}", bright(2)),
+                        Self::BrightYellow => write!(f, "{}", bright(3)),
+                        Self::BrightBlue => write!(f, "{}", bright(4)),
+                        Self::BrightMagenta => write!(f, "{}", bright(5)),
+                        Self::BrightCyan => write!(f, "{}", bright(6)),
+                        Self::BrightWhite => write!(f, "{}", bright(7)),
+                        Self::Indexed(i) => write!(f, "{};5;{}", named(8), i),
+                        Self::Rgb(r, g, b) => write!(f, "{};2;{};{};{}", named(8), r, g, b),
+                }
+        }
+}
+
+/// Set of text modifiers of [`Style`].
+///
+/// # Usage
+///
+/// ```
+/// use ruterm::view::Modifier;
+///
+/// let modifier = Modifier::BOLD | Modifier::ITALIC;
+/// assert!(modifier.contains(Modifier::BOLD));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifier(u8);
+
+impl Modifier
+{
+        pub const NONE: Self = Self(0);
+        pub const BOLD: Self = Self(1);
+        pub const DIM: Self = Self(1 << 1);
+        pub const ITALIC: Self = Self(1 << 2);
+        pub const UNDERLINE: Self = Self(1 << 3);
+        pub const BLINK: Self = Self(1 << 4);
+        pub const REVERSE: Self = Self(1 << 5);
+        pub const STRIKE: Self = Self(1 << 6);
+
+        /// SGR parameters which enable and disable each modifier.
+        const CODES: [(Self, u8, u8); 7] = [
+                (Self::BOLD, 1, 22),
+                (Self::DIM, 2, 22),
+                (Self::ITALIC, 3, 23),
+                (Self::UNDERLINE, 4, 24),
+                (Self::BLINK, 5, 25),
+                (Self::REVERSE, 7, 27),
+                (Self::STRIKE, 9, 29),
+        ];
+
+        pub fn contains(self, other: Self) -> bool
+        {
+                self.0 & other.0 == other.0
+        }
+
+        pub fn is_empty(self) -> bool
+        {
+                self.0 == 0
+        }
+
+        pub fn remove(&mut self, other: Self)
+        {
+                self.0 &= !other.0;
+        }
+}
+
+impl BitOr for Modifier
+{
+        type Output = Self;
+
+        fn bitor(self, other: Self) -> Self
+        {
+                Self(self.0 | other.0)
+        }
+}
+
+impl BitOrAssign for Modifier
+{
+        fn bitor_assign(&mut self, other: Self)
+        {
+                self.0 |= other.0;
+        }
+}
+
+/// Text style.
+///
+/// Renders to a single SGR sequence via [`Display`]. Typed alternative to [`color`] and [`style`]
+/// constants.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         tio::write,
+///         view::{
+///                 Color,
+///                 Modifier,
+///                 Style,
+///                 RESET,
+///         },
+/// };
+///
+/// let style = Style::new().fg(Color::Red).bg(Color::Rgb(0, 0, 64)).modifier(Modifier::BOLD);
+/// write(format!("{style}This is a bold red text{RESET}")).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style
+{
+        pub fg: Color,
+        pub bg: Color,
+        pub modifier: Modifier,
+}
+
+impl Style
+{
+        pub const fn new() -> Self
+        {
+                Self {
+                        fg: Color::Reset,
+                        bg: Color::Reset,
+                        modifier: Modifier::NONE,
+                }
+        }
+
+        pub fn fg(mut self, color: Color) -> Self
+        {
+                self.fg = color;
+                self
+        }
+
+        pub fn bg(mut self, color: Color) -> Self
+        {
+                self.bg = color;
+                self
+        }
+
+        /// Adds `modifier` to the style.
+        pub fn modifier(mut self, modifier: Modifier) -> Self
+        {
+                self.modifier |= modifier;
+                self
+        }
+
+        /// Minimal SGR sequence which turns `previous` style into this one.
+        ///
+        /// # Usage
+        ///
+        /// ```
+        /// use ruterm::view::{
+        ///         Color,
+        ///         Style,
+        /// };
+        ///
+        /// let red = Style::new().fg(Color::Red);
+        /// let blue = red.bg(Color::Blue);
+        /// assert_eq!("\x1b[44m", blue.diff(red).to_string());
+        /// ```
+        pub fn diff(self, previous: Style) -> Diff
+        {
+                Diff {
+                        previous,
+                        next: self,
+                }
+        }
+}
+
+impl Display for Style
+{
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+        {
+                self.diff(Style::new()).fmt(f)
+        }
+}
+
+/// Transition between two styles. Returned by [`Style::diff()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diff
+{
+        previous: Style,
+        next: Style,
+}
+
+impl Display for Diff
+{
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+        {
+                let (previous, next) = (self.previous, self.next);
+                let mut first = true;
+                let mut parameter = |f: &mut Formatter<'_>| {
+                        let separator = if first { "\x1b[" } else { ";" };
+                        first = false;
+                        write!(f, "{}", separator)
+                };
+                // bold and dim are disabled by the same parameter
+                let intensity = Modifier::BOLD | Modifier::DIM;
+                let reset_intensity = Modifier::CODES[..2].iter().any(|(modifier, _, _)| {
+                        previous.modifier.contains(*modifier) && !next.modifier.contains(*modifier)
+                });
+                if reset_intensity {
+                        parameter(f)?;
+                        write!(f, "22")?;
+                }
+                for (modifier, on, off) in Modifier::CODES {
+                        let was = previous.modifier.contains(modifier)
+                                && !(reset_intensity && intensity.contains(modifier));
+                        let is = next.modifier.contains(modifier);
+                        if was != is {
+                                parameter(f)?;
+                                write!(f, "{}", if is { on } else { off })?;
+                        }
+                }
+                if previous.fg != next.fg {
+                        parameter(f)?;
+                        next.fg.write_parameters(f, 30)?;
+                }
+                if previous.bg != next.bg {
+                        parameter(f)?;
+                        next.bg.write_parameters(f, 40)?;
+                }
+                if !first {
+                        write!(f, "m")?;
+                }
+                Ok(())
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        fn style_()
+        {
+                assert_eq!("", Style::new().to_string());
+                assert_eq!(
+                        "\x1b[1;3;31;48;5;200m",
+                        Style::new()
+                                .fg(Color::Red)
+                                .bg(Color::Indexed(200))
+                                .modifier(Modifier::BOLD | Modifier::ITALIC)
+                                .to_string()
+                );
+                assert_eq!(
+                        "\x1b[38;2;1;2;3m",
+                        Style::new().fg(Color::Rgb(1, 2, 3)).to_string()
+                );
+        }
+
+        #[test]
+        fn diff_()
+        {
+                let bold = Style::new().modifier(Modifier::BOLD | Modifier::DIM);
+                let dim = Style::new().modifier(Modifier::DIM).fg(Color::BrightBlue);
+                assert_eq!("\x1b[22;2;94m", dim.diff(bold).to_string());
+                assert_eq!("\x1b[22;39m", Style::new().diff(dim).to_string());
+                assert_eq!("", dim.diff(dim).to_string());
+        }
+}
@@ -0,0 +1,370 @@
+#[cfg(feature = "view")]
+use crate::view::{
+        Style,
+        RESET,
+};
+use crate::{
+        cursor::{
+                self,
+                Direction,
+                Shape,
+        },
+        error::Result,
+        screen,
+};
+use std::{
+        fmt::{
+                Arguments,
+                Display,
+        },
+        io::{
+                self,
+                Stdout,
+                Write,
+        },
+        sync::atomic::Ordering,
+};
+
+/// Buffered terminal writer.
+///
+/// Collects text and escape sequences in memory, without intermediate strings, and writes them
+/// to the output at once in [`TermWriter::flush()`]. Each method mirrors a function of
+/// [`crate::cursor`], [`crate::clear`], [`crate::scroll`] or [`crate::screen`].
+///
+/// Also implements [`Write`], so it can be passed to `_with_output` functions.
+///
+/// # Usage
+///
+/// ```no_run
+/// use ruterm::{
+///         view::{
+///                 Color,
+///                 Style,
+///         },
+///         writer::TermWriter,
+/// };
+///
+/// let mut writer = TermWriter::stdout();
+/// writer.hide()
+///         .set(1, 1)
+///         .clear_line()
+///         .style(Style::new().fg(Color::Green))
+///         .text("Ready")
+///         .reset();
+/// writer.flush().unwrap(); // one write per frame
+/// ```
+pub struct TermWriter<W: Write>
+{
+        output: W,
+        buffer: Vec<u8>,
+}
+
+impl TermWriter<Stdout>
+{
+        /// Creates writer to stdout.
+        pub fn stdout() -> Self
+        {
+                Self::new(io::stdout())
+        }
+}
+
+impl<W: Write> TermWriter<W>
+{
+        pub fn new(output: W) -> Self
+        {
+                Self::with_capacity(output, 8 * 1024)
+        }
+
+        pub fn with_capacity(output: W, capacity: usize) -> Self
+        {
+                Self {
+                        output,
+                        buffer: Vec::with_capacity(capacity),
+                }
+        }
+
+        pub fn get_ref(&self) -> &W
+        {
+                &self.output
+        }
+
+        /// Bytes waiting for [`TermWriter::flush()`].
+        pub fn buffer(&self) -> &[u8]
+        {
+                &self.buffer
+        }
+
+        /// Writes buffered bytes to the output and flushes it.
+        pub fn flush(&mut self) -> Result<()>
+        {
+                self.output.write_all(&self.buffer)?;
+                self.buffer.clear();
+                Ok(self.output.flush()?)
+        }
+
+        fn sequence(&mut self, arguments: Arguments) -> &mut Self
+        {
+                // writing to a vector never fails
+                let _ = self.buffer.write_fmt(arguments);
+                self
+        }
+
+        pub fn text(&mut self, text: &str) -> &mut Self
+        {
+                self.buffer.extend_from_slice(text.as_bytes());
+                self
+        }
+
+        /// Writes anything which implements [`Display`], e.g. numbers.
+        pub fn display<T: Display>(&mut self, value: T) -> &mut Self
+        {
+                self.sequence(format_args!("{}", value))
+        }
+
+        /// Same as [`crate::cursor::set()`].
+        pub fn set(&mut self, x: u16, y: u16) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{};{}H", y, x))
+        }
+
+        /// Same as [`crate::cursor::move_()`].
+        pub fn move_(&mut self, direction: Direction, distance: u16) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{}{}", distance, direction))
+        }
+
+        /// Same as [`crate::cursor::start()`].
+        pub fn start(&mut self) -> &mut Self
+        {
+                self.text("\x1b[H\x1b[2J")
+        }
+
+        /// Same as [`crate::cursor::hide()`].
+        pub fn hide(&mut self) -> &mut Self
+        {
+                cursor::HIDDEN.store(true, Ordering::Relaxed);
+                self.text("\x1b[?25l")
+        }
+
+        /// Same as [`crate::cursor::show()`].
+        pub fn show(&mut self) -> &mut Self
+        {
+                cursor::HIDDEN.store(false, Ordering::Relaxed);
+                self.text("\x1b[?25h")
+        }
+
+        /// Same as [`crate::cursor::save()`].
+        pub fn save(&mut self) -> &mut Self
+        {
+                self.text("\x1b7")
+        }
+
+        /// Same as [`crate::cursor::restore()`].
+        pub fn restore(&mut self) -> &mut Self
+        {
+                self.text("\x1b8")
+        }
+
+        /// Same as [`crate::cursor::save_position()`].
+        pub fn save_position(&mut self) -> &mut Self
+        {
+                self.text("\x1b[s")
+        }
+
+        /// Same as [`crate::cursor::restore_position()`].
+        pub fn restore_position(&mut self) -> &mut Self
+        {
+                self.text("\x1b[u")
+        }
+
+        /// Same as [`crate::cursor::set_shape()`].
+        pub fn shape(&mut self, shape: Shape) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{} q", shape))
+        }
+
+        /// Same as [`crate::cursor::set_color()`].
+        pub fn cursor_color(&mut self, r: u8, g: u8, b: u8) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b]12;#{:02x}{:02x}{:02x}\x07", r, g, b))
+        }
+
+        /// Same as [`crate::cursor::reset_color()`].
+        pub fn reset_cursor_color(&mut self) -> &mut Self
+        {
+                self.text("\x1b]112\x07")
+        }
+
+        /// Applies `style`. See [`Style`].
+        #[cfg(feature = "view")]
+        pub fn style(&mut self, style: Style) -> &mut Self
+        {
+                self.sequence(format_args!("{}", style))
+        }
+
+        /// Switches from `previous` to `next` style with minimal SGR sequence. See [`Style::diff()`].
+        #[cfg(feature = "view")]
+        pub fn style_diff(&mut self, previous: Style, next: Style) -> &mut Self
+        {
+                self.sequence(format_args!("{}", next.diff(previous)))
+        }
+
+        /// Resets style. See [`RESET`].
+        #[cfg(feature = "view")]
+        pub fn reset(&mut self) -> &mut Self
+        {
+                self.text(RESET)
+        }
+
+        /// Same as [`crate::clear::all()`].
+        pub fn clear_all(&mut self) -> &mut Self
+        {
+                self.text("\x1b[2J")
+        }
+
+        /// Same as [`crate::clear::below()`].
+        pub fn clear_below(&mut self) -> &mut Self
+        {
+                self.text("\x1b[J")
+        }
+
+        /// Same as [`crate::clear::above()`].
+        pub fn clear_above(&mut self) -> &mut Self
+        {
+                self.text("\x1b[1J")
+        }
+
+        /// Same as [`crate::clear::scrollback()`].
+        pub fn clear_scrollback(&mut self) -> &mut Self
+        {
+                self.text("\x1b[3J")
+        }
+
+        /// Same as [`crate::clear::line()`].
+        pub fn clear_line(&mut self) -> &mut Self
+        {
+                self.text("\x1b[2K")
+        }
+
+        /// Same as [`crate::clear::to_end_of_line()`].
+        pub fn clear_to_end_of_line(&mut self) -> &mut Self
+        {
+                self.text("\x1b[K")
+        }
+
+        /// Same as [`crate::clear::to_start_of_line()`].
+        pub fn clear_to_start_of_line(&mut self) -> &mut Self
+        {
+                self.text("\x1b[1K")
+        }
+
+        /// Same as [`crate::scroll::up()`].
+        pub fn scroll_up(&mut self, lines: u16) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{}S", lines))
+        }
+
+        /// Same as [`crate::scroll::down()`].
+        pub fn scroll_down(&mut self, lines: u16) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{}T", lines))
+        }
+
+        /// Same as [`crate::scroll::set_region()`].
+        pub fn set_scroll_region(&mut self, top: u16, bottom: u16) -> &mut Self
+        {
+                self.sequence(format_args!("\x1b[{};{}r", top, bottom))
+        }
+
+        /// Same as [`crate::scroll::reset_region()`].
+        pub fn reset_scroll_region(&mut self) -> &mut Self
+        {
+                self.text("\x1b[r")
+        }
+
+        /// Same as [`crate::screen::enter_alternate_screen()`].
+        pub fn enter_alternate_screen(&mut self) -> &mut Self
+        {
+                screen::ALTERNATE.store(true, Ordering::Relaxed);
+                self.text("\x1b[?1049h")
+        }
+
+        /// Same as [`crate::screen::begin_synchronized_update()`].
+        pub fn begin_synchronized_update(&mut self) -> &mut Self
+        {
+                self.text("\x1b[?2026h")
+        }
+
+        /// Same as [`crate::screen::end_synchronized_update()`].
+        pub fn end_synchronized_update(&mut self) -> &mut Self
+        {
+                self.text("\x1b[?2026l")
+        }
+
+        /// Same as [`crate::screen::leave_alternate_screen()`].
+        pub fn leave_alternate_screen(&mut self) -> &mut Self
+        {
+                screen::ALTERNATE.store(false, Ordering::Relaxed);
+                self.text("\x1b[?1049l")
+        }
+}
+
+impl<W: Write> Write for TermWriter<W>
+{
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize>
+        {
+                self.buffer.extend_from_slice(bytes);
+                Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+                Ok(TermWriter::flush(self)?)
+        }
+}
+
+impl<W: Write> Drop for TermWriter<W>
+{
+        fn drop(&mut self)
+        {
+                let _ = TermWriter::flush(self);
+        }
+}
+
+#[cfg(test)]
+mod tests
+{
+        use super::*;
+
+        #[test]
+        fn flush_()
+        {
+                let mut output = Vec::new();
+                {
+                        let mut writer = TermWriter::new(&mut output);
+                        writer.set(3, 2)
+                                .clear_line()
+                                .text("hi")
+                                .move_(Direction::Left, 2);
+                        assert_eq!(b"\x1b[2;3H\x1b[2Khi\x1b[2D", writer.buffer());
+                        assert!(writer.get_ref().is_empty());
+                        writer.flush().unwrap();
+                        assert!(writer.buffer().is_empty());
+                }
+                assert_eq!(b"\x1b[2;3H\x1b[2Khi\x1b[2D".to_vec(), output);
+        }
+
+        #[test]
+        fn cursor_()
+        {
+                let mut writer = TermWriter::new(Vec::new());
+                writer.save_position()
+                        .cursor_color(255, 0, 16)
+                        .reset_cursor_color()
+                        .restore_position();
+                assert_eq!(
+                        b"\x1b[s\x1b]12;#ff0010\x07\x1b]112\x07\x1b[u",
+                        writer.buffer()
+                );
+        }
+}